@@ -0,0 +1,143 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::submit::Verdict;
+
+/// A tab-separated record of every answer submitted, one per line:
+/// `<unix time>\t<day>\t<part>\t<answer>\t<verdict>`.
+pub struct AnswerLog {
+    path: PathBuf,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub timestamp: u64,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl AnswerLog {
+    pub fn new(path: &Path) -> Self {
+        AnswerLog {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// All recorded entries, oldest first. A missing log has no entries.
+    pub fn entries(&self) -> io::Result<Vec<Entry>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        Ok(contents.lines().filter_map(parse_entry).collect())
+    }
+
+    /// The most recent final verdict for this exact answer, if there is one.
+    pub fn find(&self, day: u32, part: u32, answer: &str) -> io::Result<Option<Entry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .rev()
+            .find(|e| e.day == day && e.part == part && e.answer == answer && e.verdict.is_final()))
+    }
+
+    /// Appends an entry. Fails like `check_answer` if the answer can't be
+    /// logged.
+    pub fn record(&self, day: u32, part: u32, answer: &str, verdict: &Verdict) -> io::Result<()> {
+        check_answer(answer)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}",
+            timestamp,
+            day,
+            part,
+            answer,
+            verdict.tag()
+        )
+    }
+}
+
+/// Fails with `io::ErrorKind::InvalidInput` if the answer contains a tab or
+/// newline, which would break up its line in the log.
+pub fn check_answer(answer: &str) -> io::Result<()> {
+    if answer.contains(['\t', '\n', '\r']) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "can't log an answer containing a tab or newline: {:?}",
+                answer
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let fields: [_; 5] = line.split('\t').collect::<Vec<_>>().try_into().ok()?;
+
+    Some(Entry {
+        timestamp: fields[0].parse().ok()?,
+        day: fields[1].parse().ok()?,
+        part: fields[2].parse().ok()?,
+        answer: fields[3].to_string(),
+        verdict: Verdict::from_tag(fields[4])?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(
+            parse_entry("1638316800\t1\t2\t1571\ttoo-low"),
+            Some(Entry {
+                timestamp: 1638316800,
+                day: 1,
+                part: 2,
+                answer: "1571".to_string(),
+                verdict: Verdict::TooLow,
+            })
+        );
+        assert_eq!(parse_entry("1638316800\t1\t2\t1571"), None);
+        assert_eq!(parse_entry("1638316800\t1\t2\t1571\tmaybe"), None);
+    }
+
+    #[test]
+    fn test_record_rejects_separators() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.tsv", std::process::id()));
+        let log = AnswerLog::new(&path);
+
+        for answer in ["15\t71", "1571\n", "1571\r"] {
+            let err = log.record(1, 2, answer, &Verdict::TooLow).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+        assert!(!path.exists());
+
+        log.record(1, 2, "1571", &Verdict::TooLow).unwrap();
+        let entries = log.entries().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].answer, "1571");
+    }
+}
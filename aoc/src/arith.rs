@@ -0,0 +1,110 @@
+//! Arithmetic for the day crates that fails with `OverflowError` instead of
+//! wrapping when the `checked` feature is on. Each day forwards its own
+//! `checked` feature here and turns it on for its tests.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+/// Returned when a checked operation overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl Error for OverflowError {}
+
+/// A number `add`, `sub` and `mul` work on.
+pub trait Checked: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    /// `None` on overflow. Floats never overflow.
+    fn try_add(self, other: Self) -> Option<Self>;
+
+    /// `None` on overflow. Floats never overflow.
+    fn try_sub(self, other: Self) -> Option<Self>;
+
+    /// `None` on overflow. Floats never overflow.
+    fn try_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Checked for $t {
+                fn try_add(self, other: Self) -> Option<Self> {
+                    self.checked_add(other)
+                }
+
+                fn try_sub(self, other: Self) -> Option<Self> {
+                    self.checked_sub(other)
+                }
+
+                fn try_mul(self, other: Self) -> Option<Self> {
+                    self.checked_mul(other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_checked_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl Checked for $t {
+                fn try_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+
+                fn try_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other)
+                }
+
+                fn try_mul(self, other: Self) -> Option<Self> {
+                    Some(self * other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_for_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+impl_checked_for_float!(f32, f64);
+
+pub fn add<T: Checked>(a: T, b: T) -> Result<T, OverflowError> {
+    checked(a.try_add(b), || a + b)
+}
+
+pub fn sub<T: Checked>(a: T, b: T) -> Result<T, OverflowError> {
+    checked(a.try_sub(b), || a - b)
+}
+
+pub fn mul<T: Checked>(a: T, b: T) -> Result<T, OverflowError> {
+    checked(a.try_mul(b), || a * b)
+}
+
+fn checked<T>(result: Option<T>, unchecked: impl FnOnce() -> T) -> Result<T, OverflowError> {
+    if cfg!(any(test, feature = "checked")) {
+        result.ok_or(OverflowError)
+    } else {
+        Ok(unchecked())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(add(u32::MAX, 0), Ok(u32::MAX));
+        assert_eq!(add(u32::MAX, 1), Err(OverflowError));
+        assert_eq!(sub(0u32, 1), Err(OverflowError));
+        assert_eq!(sub(i64::MIN, 1), Err(OverflowError));
+        assert_eq!(mul(1u32 << 16, 1 << 15), Ok(1 << 31));
+        assert_eq!(mul(1u32 << 16, 1 << 16), Err(OverflowError));
+        assert_eq!(add(f64::MAX, f64::MAX), Ok(f64::INFINITY));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod answer_log;
+pub mod arith;
+pub mod mock_server;
+pub mod perf;
+pub mod solve;
+pub mod stats;
+pub mod submit;
+pub mod timing;
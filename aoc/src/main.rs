@@ -0,0 +1,204 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc::answer_log::{check_answer, AnswerLog};
+use aoc::perf::{compare, current_commit, Change, History, Sample};
+use aoc::solve::{build_day, find_answer, list_days, run_day, time_day};
+use aoc::submit::{Client, DEFAULT_ENDPOINT};
+
+const USAGE: &str = "Usage:
+    aoc submit <day> <part> [--answer <answer>] [--endpoint <url>] [--log <file>]
+    aoc perf record [--runs <n>] [--history <file>]
+    aoc perf diff [<before> [<after>]] [--history <file>]
+
+Run from the repository root. The session cookie is read from AOC_SESSION, and the
+endpoint can also be given with AOC_ENDPOINT.";
+
+const DEFAULT_ANSWER_LOG: &str = "answers.log";
+const DEFAULT_PERF_HISTORY: &str = "perf-history.tsv";
+const DEFAULT_PERF_RUNS: u32 = 10;
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.first().map(String::as_str) {
+        Some("submit") => submit(&args[1..]),
+        Some("perf") => match args.get(1).map(String::as_str) {
+            Some("record") => perf_record(&args[2..]),
+            Some("diff") => perf_diff(&args[2..]),
+            _ => Err(USAGE.to_string()),
+        },
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn submit(args: &[String]) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut answer = None;
+    let mut endpoint = env::var("AOC_ENDPOINT").ok();
+    let mut log = PathBuf::from(DEFAULT_ANSWER_LOG);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().cloned().ok_or_else(|| USAGE.to_string());
+
+        match arg.as_str() {
+            "--answer" => answer = Some(value()?),
+            "--endpoint" => endpoint = Some(value()?),
+            "--log" => log = PathBuf::from(value()?),
+            _ => positional.push(arg),
+        }
+    }
+
+    let [day, part]: [u32; 2] = positional
+        .iter()
+        .map(|a| a.parse())
+        .collect::<Result<Vec<_>, _>>()
+        .ok()
+        .and_then(|v| v.try_into().ok())
+        .ok_or_else(|| USAGE.to_string())?;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let output = run_day(Path::new("."), day).map_err(|e| e.to_string())?;
+            find_answer(&output, part)
+                .ok_or_else(|| format!("day {} did not print an answer for part {}", day, part))?
+                .to_string()
+        }
+    };
+
+    // Refuse answers the log can't hold before anything is sent.
+    check_answer(&answer).map_err(|e| e.to_string())?;
+
+    let log = AnswerLog::new(&log);
+
+    if let Some(entry) = log.find(day, part, &answer).map_err(|e| e.to_string())? {
+        println!(
+            "Already submitted {} for day {} part {}: {}",
+            answer, day, part, entry.verdict
+        );
+        return Ok(());
+    }
+
+    let session = env::var("AOC_SESSION").map_err(|_| "No session cookie; set AOC_SESSION")?;
+    let client = Client::new(endpoint.as_deref().unwrap_or(DEFAULT_ENDPOINT), &session);
+
+    let verdict = client
+        .submit(day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    log.record(day, part, &answer, &verdict)
+        .map_err(|e| e.to_string())?;
+
+    println!("Day {} part {}: {} is {}", day, part, answer, verdict);
+
+    Ok(())
+}
+
+fn perf_record(args: &[String]) -> Result<(), String> {
+    let mut runs = DEFAULT_PERF_RUNS;
+    let mut history = PathBuf::from(DEFAULT_PERF_HISTORY);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().cloned().ok_or_else(|| USAGE.to_string());
+
+        match arg.as_str() {
+            "--runs" => runs = value()?.parse().map_err(|_| USAGE.to_string())?,
+            "--history" => history = PathBuf::from(value()?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let root = Path::new(".");
+    let commit = current_commit(root).map_err(|e| e.to_string())?;
+    let mut samples = Vec::new();
+
+    for day in list_days(root).map_err(|e| e.to_string())? {
+        build_day(root, day).map_err(|e| e.to_string())?;
+
+        for _ in 0..runs {
+            for (label, nanos) in time_day(root, day).map_err(|e| e.to_string())? {
+                samples.push(Sample {
+                    commit: commit.clone(),
+                    day,
+                    label,
+                    nanos,
+                });
+            }
+        }
+
+        println!("Timed day {:02}", day);
+    }
+
+    History::new(&history)
+        .append(&samples)
+        .map_err(|e| e.to_string())?;
+
+    println!("Recorded {} samples for {}", samples.len(), commit);
+
+    Ok(())
+}
+
+fn perf_diff(args: &[String]) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut history = PathBuf::from(DEFAULT_PERF_HISTORY);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--history" => {
+                history = PathBuf::from(iter.next().ok_or_else(|| USAGE.to_string())?);
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let history = History::new(&history);
+    let commits = history.commits().map_err(|e| e.to_string())?;
+
+    // By default, compare the two most recently recorded commits.
+    let (before, after) = match positional.as_slice() {
+        [] => match commits.as_slice() {
+            [.., before, after] => (before.clone(), after.clone()),
+            _ => return Err("Need at least two recorded commits to compare".to_string()),
+        },
+        [before] => match commits.last() {
+            Some(after) => (before.clone(), after.clone()),
+            None => return Err("No recorded commits".to_string()),
+        },
+        [before, after] => (before.clone(), after.clone()),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    for commit in [&before, &after] {
+        if !commits.contains(commit) {
+            return Err(format!("No timings recorded for {}", commit));
+        }
+    }
+
+    let samples = history.samples().map_err(|e| e.to_string())?;
+    let comparisons = compare(&samples, &before, &after);
+
+    println!("{} -> {}", before, after);
+    for c in comparisons.iter() {
+        println!("{}", c);
+    }
+
+    let slowdowns = comparisons
+        .iter()
+        .filter(|c| c.change == Change::Slower)
+        .count();
+
+    if slowdowns > 0 {
+        return Err(format!("{} significant slowdown(s)", slowdowns));
+    }
+
+    Ok(())
+}
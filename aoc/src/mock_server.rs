@@ -0,0 +1,203 @@
+//! A minimal HTTP server that stands in for the puzzle site, so the submission
+//! flow can be tested offline.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Looks up `key` in a `application/x-www-form-urlencoded` body.
+    pub fn form_value(&self, key: &str) -> Option<String> {
+        self.body
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| url_decode(v))
+    }
+}
+
+/// Serves every request with the `(status, body)` returned by its handler,
+/// and remembers the requests it received. Shuts down when dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start<F>(handler: F) -> io::Result<Self>
+    where
+        F: Fn(&Request) -> (u16, String) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let requests = Arc::clone(&requests);
+            let shutdown = Arc::clone(&shutdown);
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if let Ok(stream) = stream {
+                        // A malformed request only affects that connection.
+                        let _ = serve(stream, &handler, &requests);
+                    }
+                }
+            })
+        };
+
+        Ok(MockServer {
+            addr,
+            requests,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// The base URL to use in place of the real puzzle site.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        // Wake the accept loop so it notices the shutdown flag.
+        let _ = TcpStream::connect(self.addr);
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve<F>(stream: TcpStream, handler: &F, requests: &Mutex<Vec<Request>>) -> io::Result<()>
+where
+    F: Fn(&Request) -> (u16, String),
+{
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+
+        match line.trim_end().split_once(':') {
+            Some((k, v)) => headers.push((k.trim().to_string(), v.trim().to_string())),
+            None => break,
+        }
+    }
+
+    let content_length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let request = Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    };
+
+    let (status, body) = handler(&request);
+    requests.lock().unwrap().push(request);
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} Mock\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn url_decode(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+
+    while let Some(b) = iter.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [iter.next().unwrap_or(b'0'), iter.next().unwrap_or(b'0')];
+                let decoded = std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                bytes.push(decoded.unwrap_or(b'?'));
+            }
+            b => bytes.push(b),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_url_decode() {
+        assert_eq!(url_decode("1571"), "1571");
+        assert_eq!(url_decode("a+b%2Cc"), "a b,c");
+    }
+
+    #[test]
+    fn test_form_value() {
+        let request = Request {
+            method: "POST".to_string(),
+            path: "/".to_string(),
+            headers: Vec::new(),
+            body: "level=1&answer=abc%20def".to_string(),
+        };
+
+        assert_eq!(request.form_value("level"), Some("1".to_string()));
+        assert_eq!(request.form_value("answer"), Some("abc def".to_string()));
+        assert_eq!(request.form_value("day"), None);
+    }
+}
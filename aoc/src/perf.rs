@@ -0,0 +1,243 @@
+//! Records solver timings per commit, and compares them between commits.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::stats::{mean, Welch};
+
+/// A single timing of one labelled step (usually `Part 1` or `Part 2`) of a
+/// day's solver.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    pub commit: String,
+    pub day: u32,
+    pub label: String,
+    pub nanos: u64,
+}
+
+/// A tab-separated file of samples, one per line:
+/// `<commit>\t<day>\t<label>\t<nanos>`. Samples from a single recording are
+/// contiguous, so commits appear in the order they were recorded.
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn new(path: &Path) -> Self {
+        History {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// All recorded samples, oldest first. A missing history has no samples.
+    pub fn samples(&self) -> io::Result<Vec<Sample>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        Ok(contents.lines().filter_map(parse_sample).collect())
+    }
+
+    pub fn append(&self, samples: &[Sample]) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        for s in samples {
+            writeln!(file, "{}\t{}\t{}\t{}", s.commit, s.day, s.label, s.nanos)?;
+        }
+
+        Ok(())
+    }
+
+    /// Recorded commits, in the order they were first recorded.
+    pub fn commits(&self) -> io::Result<Vec<String>> {
+        let mut commits: Vec<String> = Vec::new();
+
+        for s in self.samples()? {
+            if !commits.contains(&s.commit) {
+                commits.push(s.commit);
+            }
+        }
+
+        Ok(commits)
+    }
+}
+
+fn parse_sample(line: &str) -> Option<Sample> {
+    let fields: [_; 4] = line.split('\t').collect::<Vec<_>>().try_into().ok()?;
+
+    Some(Sample {
+        commit: fields[0].to_string(),
+        day: fields[1].parse().ok()?,
+        label: fields[2].to_string(),
+        nanos: fields[3].parse().ok()?,
+    })
+}
+
+/// The current commit, suffixed with `-dirty` if the working tree has
+/// uncommitted changes.
+pub fn current_commit(root: &Path) -> io::Result<String> {
+    let git = |args: &[&str]| -> io::Result<String> {
+        let output = Command::new("git").current_dir(root).args(args).output()?;
+
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+
+    if git(&["status", "--porcelain"])?.is_empty() {
+        Ok(commit)
+    } else {
+        Ok(format!("{}-dirty", commit))
+    }
+}
+
+/// How one solver step changed between two commits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub label: String,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+    pub change: Change,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Slower,
+    Faster,
+    /// No statistically significant difference.
+    Unchanged,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = (self.after_nanos / self.before_nanos - 1.0) * 100.0;
+        let change = match self.change {
+            Change::Slower => "SLOWER",
+            Change::Faster => "faster",
+            Change::Unchanged => "",
+        };
+
+        write!(
+            f,
+            "day {:02} {:<8} {:>14.0}ns -> {:>14.0}ns {:>+8.1}% {}",
+            self.day, self.label, self.before_nanos, self.after_nanos, percent, change
+        )
+    }
+}
+
+/// Compares every step timed at both commits.
+pub fn compare(samples: &[Sample], before: &str, after: &str) -> Vec<Comparison> {
+    let group = |commit: &str| {
+        let mut groups: BTreeMap<(u32, String), Vec<f64>> = BTreeMap::new();
+
+        for s in samples.iter().filter(|s| s.commit == commit) {
+            groups
+                .entry((s.day, s.label.clone()))
+                .or_default()
+                .push(s.nanos as f64);
+        }
+
+        groups
+    };
+
+    let before = group(before);
+    let after = group(after);
+
+    before
+        .iter()
+        .filter_map(|(key, before)| {
+            let after = after.get(key)?;
+
+            let change = match Welch::test(before, after) {
+                Some(w) if w.is_significant_increase() => Change::Slower,
+                Some(w) if w.is_significant_decrease() => Change::Faster,
+                _ => Change::Unchanged,
+            };
+
+            Some(Comparison {
+                day: key.0,
+                label: key.1.clone(),
+                before_nanos: mean(before),
+                after_nanos: mean(after),
+                change,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn samples(commit: &str, day: u32, label: &str, nanos: &[u64]) -> Vec<Sample> {
+        nanos
+            .iter()
+            .map(|&nanos| Sample {
+                commit: commit.to_string(),
+                day,
+                label: label.to_string(),
+                nanos,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        assert_eq!(
+            parse_sample("d3bb933\t5\tPart 2\t26395873"),
+            Some(Sample {
+                commit: "d3bb933".to_string(),
+                day: 5,
+                label: "Part 2".to_string(),
+                nanos: 26395873,
+            })
+        );
+        assert_eq!(parse_sample("d3bb933\t5\tPart 2"), None);
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = [
+            samples("a", 5, "Part 1", &[100, 102, 98, 101, 99]),
+            samples("a", 5, "Part 2", &[200, 202, 198, 201, 199]),
+            samples("a", 7, "Part 1", &[50, 52, 48, 51, 49]),
+            samples("b", 5, "Part 1", &[101, 99, 103, 97, 100]),
+            samples("b", 5, "Part 2", &[300, 302, 298, 301, 299]),
+            samples("b", 7, "Part 1", &[30, 32, 28, 31, 29]),
+            samples("b", 7, "Part 2", &[30, 32, 28, 31, 29]),
+        ]
+        .concat();
+
+        let changes = compare(&history, "a", "b")
+            .into_iter()
+            .map(|c| (c.day, c.label, c.change))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            vec![
+                (5, "Part 1".to_string(), Change::Unchanged),
+                (5, "Part 2".to_string(), Change::Slower),
+                (7, "Part 1".to_string(), Change::Faster),
+            ]
+        );
+    }
+}
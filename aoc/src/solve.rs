@@ -0,0 +1,158 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The crate directory for `day`, e.g. `aoc07`.
+pub fn day_dir(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("aoc{:02}", day))
+}
+
+/// Every day that has a crate directory under `root`, in order.
+pub fn list_days(root: &Path) -> io::Result<Vec<u32>> {
+    let mut days = fs::read_dir(root)?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let day = name.strip_prefix("aoc")?;
+
+            if day.len() == 2 {
+                day.parse().ok()
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// Builds the solver for `day` in release mode.
+pub fn build_day(root: &Path, day: u32) -> io::Result<()> {
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--quiet", "--manifest-path"])
+        .arg(day_dir(root, day).join("Cargo.toml"))
+        .status()?;
+
+    if !status.success() {
+        return Err(io::Error::other(format!("failed to build day {}", day)));
+    }
+
+    Ok(())
+}
+
+/// Runs an already built solver for `day`, returning the time each step took
+/// as reported with `AOC_TIMINGS`.
+pub fn time_day(root: &Path, day: u32) -> io::Result<Vec<(String, u64)>> {
+    let target_dir = env::var_os("CARGO_TARGET_DIR").map(PathBuf::from);
+    let binary = release_binary(root, day, target_dir.as_deref());
+
+    let output = Command::new(binary).env("AOC_TIMINGS", "1").output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "solver for day {} failed: {}",
+            day,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(find_timings(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// Where `cargo build --release` puts the solver for `day`: under
+/// `target_dir` if one is set, as with `CARGO_TARGET_DIR`, or else the
+/// day's own `target` directory.
+pub fn release_binary(root: &Path, day: u32, target_dir: Option<&Path>) -> PathBuf {
+    let target_dir = match target_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => day_dir(root, day).join("target"),
+    };
+
+    target_dir.join("release").join(format!("aoc{:02}", day))
+}
+
+/// Extracts the timings a solver printed as `<label> took <nanos>ns`.
+pub fn find_timings(output: &str) -> Vec<(String, u64)> {
+    output
+        .lines()
+        .filter_map(|l| {
+            let (label, nanos) = l.rsplit_once(" took ")?;
+            Some((label.to_string(), nanos.strip_suffix("ns")?.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Runs the solver for `day` in release mode and returns everything it printed.
+pub fn run_day(root: &Path, day: u32) -> io::Result<String> {
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--manifest-path"])
+        .arg(day_dir(root, day).join("Cargo.toml"))
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "solver for day {} failed: {}",
+            day,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Extracts the answer a solver printed as `Part <part>: <answer>`.
+pub fn find_answer(output: &str, part: u32) -> Option<&str> {
+    let prefix = format!("Part {}:", part);
+
+    output
+        .lines()
+        .find_map(|l| l.strip_prefix(&prefix))
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_day_dir() {
+        assert_eq!(day_dir(Path::new("/repo"), 7), Path::new("/repo/aoc07"));
+        assert_eq!(day_dir(Path::new("/repo"), 12), Path::new("/repo/aoc12"));
+    }
+
+    #[test]
+    fn test_release_binary() {
+        assert_eq!(
+            release_binary(Path::new("/repo"), 7, None),
+            Path::new("/repo/aoc07/target/release/aoc07")
+        );
+        assert_eq!(
+            release_binary(Path::new("/repo"), 7, Some(Path::new("/tmp/target"))),
+            Path::new("/tmp/target/release/aoc07")
+        );
+    }
+
+    #[test]
+    fn test_find_timings() {
+        let output = "Part 1 took 7043ns\nwarning: something\nPart 2 took 1085823ns\n";
+
+        assert_eq!(
+            find_timings(output),
+            vec![
+                ("Part 1".to_string(), 7043),
+                ("Part 2".to_string(), 1085823)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_answer() {
+        let output = "Part 1: 336131\nPart 2: 92676646\n";
+
+        assert_eq!(find_answer(output, 1), Some("336131"));
+        assert_eq!(find_answer(output, 2), Some("92676646"));
+        assert_eq!(find_answer("Part 2: 5\n", 1), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Just enough statistics to tell a real slowdown from timing noise.
+
+pub fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// The unbiased sample variance.
+pub fn variance(samples: &[f64]) -> f64 {
+    let mean = mean(samples);
+
+    samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (samples.len() - 1) as f64
+}
+
+/// The result of Welch's t-test comparing `after` against `before`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Welch {
+    pub t: f64,
+    pub degrees_of_freedom: f64,
+}
+
+impl Welch {
+    /// Needs at least two samples on each side.
+    pub fn test(before: &[f64], after: &[f64]) -> Option<Self> {
+        if before.len() < 2 || after.len() < 2 {
+            return None;
+        }
+
+        let (n1, n2) = (before.len() as f64, after.len() as f64);
+        let (v1, v2) = (variance(before) / n1, variance(after) / n2);
+        let difference = mean(after) - mean(before);
+
+        // Without any noise every difference is significant, so `t` is
+        // infinite, or zero when the means are equal.
+        if v1 + v2 == 0.0 {
+            let t = if difference == 0.0 {
+                0.0
+            } else {
+                difference.signum() * f64::INFINITY
+            };
+
+            return Some(Welch {
+                t,
+                degrees_of_freedom: n1 + n2 - 2.0,
+            });
+        }
+
+        let t = difference / (v1 + v2).sqrt();
+        let degrees_of_freedom =
+            (v1 + v2).powi(2) / (v1.powi(2) / (n1 - 1.0) + v2.powi(2) / (n2 - 1.0));
+
+        Some(Welch {
+            t,
+            degrees_of_freedom,
+        })
+    }
+
+    /// Whether `after` is larger than `before` at the 95% confidence level.
+    pub fn is_significant_increase(&self) -> bool {
+        self.t > t_critical_95(self.degrees_of_freedom)
+    }
+
+    /// Whether `after` is smaller than `before` at the 95% confidence level.
+    pub fn is_significant_decrease(&self) -> bool {
+        -self.t > t_critical_95(self.degrees_of_freedom)
+    }
+}
+
+/// One-sided 95% critical value of Student's t distribution, using the
+/// Cornish-Fisher expansion around the normal quantile.
+fn t_critical_95(degrees_of_freedom: f64) -> f64 {
+    const Z: f64 = 1.644_853_626_951_472_2;
+
+    let df = degrees_of_freedom.max(1.0);
+    let g1 = (Z.powi(3) + Z) / 4.0;
+    let g2 = (5.0 * Z.powi(5) + 16.0 * Z.powi(3) + 3.0 * Z) / 96.0;
+
+    Z + g1 / df + g2 / df.powi(2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mean_variance() {
+        assert_eq!(mean(&[1.0, 2.0, 3.0, 4.0]), 2.5);
+        assert!((variance(&[1.0, 2.0, 3.0, 4.0]) - 5.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_t_critical_95() {
+        // Reference values from a t table.
+        assert!((t_critical_95(10.0) - 1.812).abs() < 0.01);
+        assert!((t_critical_95(30.0) - 1.697).abs() < 0.01);
+        assert!((t_critical_95(1000.0) - 1.646).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_welch() {
+        let before = [100.0, 102.0, 98.0, 101.0, 99.0];
+        let noisy = [101.0, 99.0, 103.0, 97.0, 100.0];
+        let slower = [120.0, 122.0, 118.0, 121.0, 119.0];
+
+        let welch = Welch::test(&before, &slower).unwrap();
+        assert!(welch.is_significant_increase());
+        assert!(!welch.is_significant_decrease());
+
+        let welch = Welch::test(&before, &noisy).unwrap();
+        assert!(!welch.is_significant_increase());
+        assert!(!welch.is_significant_decrease());
+
+        let welch = Welch::test(&slower, &before).unwrap();
+        assert!(welch.is_significant_decrease());
+
+        assert_eq!(Welch::test(&[1.0], &slower), None);
+    }
+
+    #[test]
+    fn test_welch_without_variance() {
+        let welch = Welch::test(&[100.0, 100.0], &[200.0, 200.0]).unwrap();
+        assert!(welch.is_significant_increase());
+        assert!(!welch.is_significant_decrease());
+
+        let welch = Welch::test(&[200.0, 200.0], &[100.0, 100.0]).unwrap();
+        assert!(welch.is_significant_decrease());
+
+        let welch = Welch::test(&[100.0, 100.0], &[100.0, 100.0]).unwrap();
+        assert!(!welch.is_significant_increase());
+        assert!(!welch.is_significant_decrease());
+    }
+}
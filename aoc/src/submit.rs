@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+
+/// Posts answers to the puzzle server, authenticated with the same session
+/// cookie that `setup` uses to download inputs.
+pub struct Client {
+    endpoint: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(endpoint: &str, session: &str) -> Self {
+        Client {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Verdict, SubmitError> {
+        let url = format!("{}/2021/day/{}/answer", self.endpoint, day);
+
+        // The cookie goes in a config read from stdin rather than on the
+        // command line, where any other user could see it in `ps`.
+        let mut curl = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--config", "-"])
+            .arg("--data-urlencode")
+            .arg(format!("level={}", part))
+            .arg("--data-urlencode")
+            .arg(format!("answer={}", answer))
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(SubmitError::Io)?;
+
+        let config = format!("cookie = \"session={}\"\n", escape_config(&self.session));
+        // Dropping stdin closes it, so curl stops waiting for more config.
+        let written = curl.stdin.take().unwrap().write_all(config.as_bytes());
+        let output = curl.wait_with_output().map_err(SubmitError::Io)?;
+        written.map_err(SubmitError::Io)?;
+
+        if !output.status.success() {
+            return Err(SubmitError::Http(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(Verdict::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// Escapes `value` for a double-quoted string in a curl config file.
+fn escape_config(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The server's response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Answers were submitted too quickly. Holds the remaining wait, if the
+    /// server said how long it is.
+    RateLimited(Option<String>),
+    /// The part has already been solved, or isn't unlocked yet.
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn parse(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Verdict::Correct
+        } else if body.contains("You gave an answer too recently") {
+            let wait = body
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+
+            Verdict::RateLimited(wait)
+        } else if body.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else if body.contains("That's not the right answer") {
+            if body.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if body.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether submitting the same answer again would give the same verdict.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+
+    /// A single-word form of the verdict, used by the answer log.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "correct" => Some(Verdict::Correct),
+            "incorrect" => Some(Verdict::Incorrect),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "rate-limited" => Some(Verdict::RateLimited(None)),
+            "already-solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "right answer"),
+            Verdict::Incorrect => write!(f, "wrong answer"),
+            Verdict::TooHigh => write!(f, "wrong answer (too high)"),
+            Verdict::TooLow => write!(f, "wrong answer (too low)"),
+            Verdict::RateLimited(Some(wait)) => write!(f, "rate limited, {} left to wait", wait),
+            Verdict::RateLimited(None) => write!(f, "rate limited"),
+            Verdict::AlreadySolved => write!(f, "already solved, or not unlocked yet"),
+            Verdict::Unknown => write!(f, "unrecognised response"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// `curl` couldn't be run at all.
+    Io(io::Error),
+    /// The request failed, e.g. because the endpoint is unreachable or the
+    /// session cookie was rejected.
+    Http(String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Io(e) => write!(f, "failed to run curl: {}", e),
+            SubmitError::Http(msg) => write!(f, "request failed: {}", msg),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            Verdict::parse("<p>That's the right answer!  You are one gold star closer</p>"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse("<p>That's not the right answer.  If you're stuck</p>"),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::parse("<p>That's not the right answer; your answer is too high.</p>"),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse("<p>That's not the right answer; your answer is too low.</p>"),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse(
+                "<p>You gave an answer too recently; you have to wait after submitting an \
+                 answer before trying again.  You have 4m 32s left to wait.</p>"
+            ),
+            Verdict::RateLimited(Some("4m 32s".to_string()))
+        );
+        assert_eq!(
+            Verdict::parse("<p>You don't seem to be solving the right level.</p>"),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_escape_config() {
+        assert_eq!(escape_config("53616c746564"), "53616c746564");
+        assert_eq!(escape_config(r#"a"b\c"#), r#"a\"b\\c"#);
+    }
+
+    #[test]
+    fn test_verdict_tags() {
+        for verdict in [
+            Verdict::Correct,
+            Verdict::Incorrect,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::RateLimited(None),
+            Verdict::AlreadySolved,
+            Verdict::Unknown,
+        ] {
+            assert_eq!(Verdict::from_tag(verdict.tag()), Some(verdict));
+        }
+    }
+}
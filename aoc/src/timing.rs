@@ -0,0 +1,15 @@
+use std::env;
+use std::time::Instant;
+
+/// Runs `solve`, reporting how long it took on stderr if `AOC_TIMINGS` is
+/// set, as `<label> took <nanos>ns` for `solve::find_timings` to pick up.
+pub fn timed<T>(label: &str, solve: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = solve();
+
+    if env::var_os("AOC_TIMINGS").is_some() {
+        eprintln!("{} took {}ns", label, start.elapsed().as_nanos());
+    }
+
+    result
+}
@@ -0,0 +1,178 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc::answer_log::AnswerLog;
+use aoc::mock_server::MockServer;
+use aoc::submit::{Client, SubmitError, Verdict};
+
+/// Answers like the real puzzle site would for a puzzle whose answer is 1571.
+fn puzzle_site(request: &aoc::mock_server::Request) -> (u16, String) {
+    if request.header("cookie") != Some("session=secret") {
+        return (400, "Please log in.".to_string());
+    }
+
+    let body = match request.form_value("answer").as_deref().map(str::parse::<u32>) {
+        Some(Ok(1571)) => "<p>That's the right answer!  You are one gold star closer.</p>",
+        Some(Ok(n)) if n > 1571 => {
+            "<p>That's not the right answer; your answer is too high.  Please wait one minute.</p>"
+        }
+        Some(Ok(_)) => {
+            "<p>That's not the right answer; your answer is too low.  Please wait one minute.</p>"
+        }
+        _ => "<p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p>",
+    };
+
+    (
+        200,
+        format!(
+            "<html><body><main><article>{}</article></main></body></html>",
+            body
+        ),
+    )
+}
+
+#[test]
+fn test_submit_verdicts() {
+    let server = MockServer::start(puzzle_site).unwrap();
+    let client = Client::new(&server.endpoint(), "secret");
+
+    assert_eq!(client.submit(1, 2, "1571").unwrap(), Verdict::Correct);
+    assert_eq!(client.submit(1, 2, "2000").unwrap(), Verdict::TooHigh);
+    assert_eq!(client.submit(1, 2, "1000").unwrap(), Verdict::TooLow);
+    assert_eq!(client.submit(1, 2, "abc").unwrap(), Verdict::Incorrect);
+}
+
+#[test]
+fn test_submit_request() {
+    let server = MockServer::start(puzzle_site).unwrap();
+    let client = Client::new(&format!("{}/", server.endpoint()), "secret");
+
+    client.submit(7, 1, "336131").unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2021/day/7/answer");
+    assert_eq!(requests[0].form_value("level"), Some("1".to_string()));
+    assert_eq!(requests[0].form_value("answer"), Some("336131".to_string()));
+}
+
+#[test]
+fn test_submit_rate_limited() {
+    let server = MockServer::start(|_| {
+        (
+            200,
+            "<p>You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 37s left to wait.</p>"
+                .to_string(),
+        )
+    })
+    .unwrap();
+    let client = Client::new(&server.endpoint(), "secret");
+
+    assert_eq!(
+        client.submit(1, 1, "1532").unwrap(),
+        Verdict::RateLimited(Some("37s".to_string()))
+    );
+}
+
+#[test]
+fn test_submit_rejected_session() {
+    let server = MockServer::start(puzzle_site).unwrap();
+    let client = Client::new(&server.endpoint(), "expired");
+
+    assert!(matches!(
+        client.submit(1, 1, "1532"),
+        Err(SubmitError::Http(_))
+    ));
+}
+
+#[test]
+fn test_answer_log() {
+    let path = env::temp_dir().join(format!("aoc-answer-log-{}.log", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let server = MockServer::start(puzzle_site).unwrap();
+    let client = Client::new(&server.endpoint(), "secret");
+    let log = AnswerLog::new(&path);
+
+    for answer in ["1000", "1571"] {
+        let verdict = client.submit(1, 2, answer).unwrap();
+        log.record(1, 2, answer, &verdict).unwrap();
+    }
+
+    let entries = log.entries().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].verdict, Verdict::TooLow);
+    assert_eq!(entries[1].answer, "1571");
+    assert_eq!(entries[1].verdict, Verdict::Correct);
+
+    assert_eq!(
+        log.find(1, 2, "1000").unwrap().map(|e| e.verdict),
+        Some(Verdict::TooLow)
+    );
+    assert_eq!(log.find(1, 1, "1000").unwrap(), None);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_submit_command() {
+    let path = env::temp_dir().join(format!("aoc-submit-command-{}.log", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let server = MockServer::start(puzzle_site).unwrap();
+
+    let run = || {
+        process::Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["submit", "1", "2", "--answer", "1571"])
+            .env("AOC_SESSION", "secret")
+            .arg("--endpoint")
+            .arg(server.endpoint())
+            .arg("--log")
+            .arg(&path)
+            .output()
+            .unwrap()
+    };
+
+    let output = run();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Day 1 part 2: 1571 is right answer\n"
+    );
+
+    // The second submission is answered from the log.
+    let output = run();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Already submitted 1571 for day 1 part 2: right answer\n"
+    );
+    assert_eq!(server.requests().len(), 1);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_submit_command_rejects_separators() {
+    let path = env::temp_dir().join(format!("aoc-submit-separators-{}.log", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let server = MockServer::start(puzzle_site).unwrap();
+
+    let output = process::Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["submit", "1", "2", "--answer", "15\t71"])
+        .env("AOC_SESSION", "secret")
+        .arg("--endpoint")
+        .arg(server.endpoint())
+        .arg("--log")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(server.requests().is_empty());
+    assert!(!path.exists());
+}
@@ -0,0 +1,47 @@
+//! Compares the scalar and SIMD `count_increases` on 10^8 readings. Set
+//! `AOC01_BENCH_READINGS` to use a different number of readings.
+
+use std::env;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use aoc01::{count_increases, count_increases_simd};
+
+/// Generates `n` depth readings as a random walk, using a fixed-seed LCG so
+/// that every run benchmarks the same input.
+fn generate_input(n: usize) -> Vec<u32> {
+    let mut state: u64 = 0x2021_0001;
+    let mut depth: u32 = 1 << 20;
+
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            depth = depth.wrapping_add((state >> 59) as u32).wrapping_sub(15);
+            depth
+        })
+        .collect()
+}
+
+fn bench_count_increases(c: &mut Criterion) {
+    let readings = env::var("AOC01_BENCH_READINGS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(100_000_000);
+    let input = generate_input(readings);
+
+    let mut group = c.benchmark_group("count_increases");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(readings as u64));
+
+    group.bench_function("scalar", |b| b.iter(|| count_increases(black_box(&input))));
+    group.bench_function("simd", |b| {
+        b.iter(|| count_increases_simd(black_box(&input)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_count_increases);
+criterion_main!(benches);
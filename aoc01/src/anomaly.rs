@@ -0,0 +1,242 @@
+use std::fmt;
+
+/// Thresholds for `detect_anomalies`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnomalyConfig {
+    /// How many preceding deltas a delta is compared against.
+    pub window: usize,
+    /// How many standard deviations from the rolling mean makes a spike.
+    pub k: f64,
+    /// How many identical readings in a row are suspicious.
+    pub min_repeats: usize,
+    /// The largest plausible rise towards the surface between two readings.
+    pub max_ascent: u32,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        AnomalyConfig {
+            window: 10,
+            k: 3.0,
+            min_repeats: 3,
+            max_ascent: 100,
+        }
+    }
+}
+
+/// A suspicious reading. Line numbers are 1-based, matching the input file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anomaly {
+    pub line: usize,
+    pub kind: AnomalyKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnomalyKind {
+    /// The change from the previous reading is an outlier compared to the
+    /// changes just before it.
+    Spike { delta: i64, mean: f64, std_dev: f64 },
+    /// The reading starts a run of `count` identical readings.
+    Repeated { count: usize },
+    /// The reading is shallower than the previous one by more than the
+    /// submarine could plausibly rise.
+    ImpossibleAscent { delta: i64 },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            AnomalyKind::Spike {
+                delta,
+                mean,
+                std_dev,
+            } => write!(
+                f,
+                "line {}: spike of {:+} (rolling mean {:.2}, std dev {:.2})",
+                self.line, delta, mean, std_dev
+            ),
+            AnomalyKind::Repeated { count } => write!(
+                f,
+                "line {}: same reading repeated {} times",
+                self.line, count
+            ),
+            AnomalyKind::ImpossibleAscent { delta } => {
+                write!(f, "line {}: impossible ascent of {:+}", self.line, delta)
+            }
+        }
+    }
+}
+
+/// Flags suspicious readings in `input`, ordered by line.
+pub fn detect_anomalies(input: &[u32], config: &AnomalyConfig) -> Vec<Anomaly> {
+    let deltas = input
+        .windows(2)
+        .map(|nums| nums[1] as i64 - nums[0] as i64)
+        .collect::<Vec<_>>();
+
+    // deltas[i] is the change into input[i + 1], i.e. line i + 2.
+    let line_of_delta = |i: usize| i + 2;
+
+    let mut anomalies = Vec::new();
+
+    if config.window > 1 {
+        for (i, window) in deltas.windows(config.window + 1).enumerate() {
+            let (&delta, previous) = window.split_last().unwrap();
+
+            let mean = previous.iter().sum::<i64>() as f64 / previous.len() as f64;
+            let variance = previous
+                .iter()
+                .map(|&d| (d as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (previous.len() - 1) as f64;
+            let std_dev = variance.sqrt();
+
+            // After perfectly regular deltas the std dev is 0, so any change
+            // from the mean at all is a spike.
+            if (delta as f64 - mean).abs() > config.k * std_dev {
+                anomalies.push(Anomaly {
+                    line: line_of_delta(i + config.window),
+                    kind: AnomalyKind::Spike {
+                        delta,
+                        mean,
+                        std_dev,
+                    },
+                });
+            }
+        }
+    }
+
+    for (i, &delta) in deltas.iter().enumerate() {
+        if -delta > config.max_ascent as i64 {
+            anomalies.push(Anomaly {
+                line: line_of_delta(i),
+                kind: AnomalyKind::ImpossibleAscent { delta },
+            });
+        }
+    }
+
+    let mut run_start = 0;
+    for i in 1..=input.len() {
+        if i == input.len() || input[i] != input[run_start] {
+            let count = i - run_start;
+
+            if config.min_repeats > 1 && count >= config.min_repeats {
+                anomalies.push(Anomaly {
+                    line: run_start + 1,
+                    kind: AnomalyKind::Repeated { count },
+                });
+            }
+
+            run_start = i;
+        }
+    }
+
+    anomalies.sort_by_key(|a| a.line);
+    anomalies
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_no_anomalies() {
+        assert_eq!(
+            detect_anomalies(
+                &[199, 200, 208, 210, 200, 207, 240, 269, 260, 263],
+                &AnomalyConfig::default()
+            ),
+            vec![]
+        );
+        assert_eq!(detect_anomalies(&[], &AnomalyConfig::default()), vec![]);
+    }
+
+    #[test]
+    fn test_spike() {
+        let config = AnomalyConfig {
+            window: 4,
+            ..AnomalyConfig::default()
+        };
+
+        let anomalies = detect_anomalies(&[100, 101, 103, 104, 106, 107, 180, 181], &config);
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].line, 7);
+        assert!(matches!(
+            anomalies[0].kind,
+            AnomalyKind::Spike { delta: 73, .. }
+        ));
+    }
+
+    #[test]
+    fn test_spike_after_regular_readings() {
+        let config = AnomalyConfig {
+            window: 5,
+            ..AnomalyConfig::default()
+        };
+
+        assert_eq!(
+            detect_anomalies(&[100, 101, 102, 103, 104, 105, 106, 500], &config),
+            vec![Anomaly {
+                line: 8,
+                kind: AnomalyKind::Spike {
+                    delta: 394,
+                    mean: 1.0,
+                    std_dev: 0.0,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_repeated() {
+        let anomalies =
+            detect_anomalies(&[5, 7, 7, 7, 7, 8, 8, 9, 9, 9], &AnomalyConfig::default());
+
+        assert_eq!(
+            anomalies,
+            vec![
+                Anomaly {
+                    line: 2,
+                    kind: AnomalyKind::Repeated { count: 4 }
+                },
+                Anomaly {
+                    line: 8,
+                    kind: AnomalyKind::Repeated { count: 3 }
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_impossible_ascent() {
+        let config = AnomalyConfig {
+            max_ascent: 50,
+            ..AnomalyConfig::default()
+        };
+
+        assert_eq!(
+            detect_anomalies(&[300, 310, 200, 250, 199], &config),
+            vec![
+                Anomaly {
+                    line: 3,
+                    kind: AnomalyKind::ImpossibleAscent { delta: -110 }
+                },
+                Anomaly {
+                    line: 5,
+                    kind: AnomalyKind::ImpossibleAscent { delta: -51 }
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let anomaly = Anomaly {
+            line: 12,
+            kind: AnomalyKind::ImpossibleAscent { delta: -110 },
+        };
+
+        assert_eq!(anomaly.to_string(), "line 12: impossible ascent of -110");
+    }
+}
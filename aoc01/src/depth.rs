@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+use aoc::arith::Checked;
+
+/// A numeric reading that can be compared and summed, so that the counting
+/// functions work on any sensor series rather than just `u32` depths.
+pub trait Depth: Checked + Default + PartialOrd {
+    /// Whether sums are exact short of overflowing, so that adding and then
+    /// subtracting a value gives back the sum it started from. Not so for
+    /// floats, which round.
+    const EXACT: bool;
+}
+
+macro_rules! impl_depth {
+    ($exact:expr => $($t:ty),*) => {
+        $(
+            impl Depth for $t {
+                const EXACT: bool = $exact;
+            }
+        )*
+    };
+}
+
+impl_depth!(true => u8, u16, u32, u64, i8, i16, i32, i64);
+impl_depth!(false => f32, f64);
+
+/// How a value is compared against the one before it when counting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison<T> {
+    Increase,
+    Decrease,
+    Unchanged,
+    /// An increase by strictly more than the threshold.
+    IncreaseBeyond(T),
+}
+
+impl<T: Depth> Comparison<T> {
+    pub fn matches(&self, prev: T, next: T) -> bool {
+        match *self {
+            Comparison::Increase => next > prev,
+            Comparison::Decrease => next < prev,
+            Comparison::Unchanged => next == prev,
+            // Checking `next > prev` first keeps the subtraction from
+            // underflowing for unsigned types.
+            Comparison::IncreaseBeyond(threshold) => next > prev && next - prev > threshold,
+        }
+    }
+}
+
+/// Parses `increase`, `decrease`, `unchanged` or `increase-beyond:<threshold>`.
+impl<T: FromStr> FromStr for Comparison<T> {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "increase" => Ok(Comparison::Increase),
+            None if s == "decrease" => Ok(Comparison::Decrease),
+            None if s == "unchanged" => Ok(Comparison::Unchanged),
+            Some(("increase-beyond", threshold)) => threshold
+                .parse()
+                .map(Comparison::IncreaseBeyond)
+                .map_err(|_| ()),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_comparison_matches() {
+        assert!(Comparison::Increase.matches(1u32, 2));
+        assert!(!Comparison::Increase.matches(2u32, 2));
+        assert!(Comparison::Decrease.matches(2u32, 1));
+        assert!(!Comparison::Decrease.matches(1u32, 2));
+        assert!(Comparison::Unchanged.matches(2u32, 2));
+        assert!(!Comparison::Unchanged.matches(2u32, 3));
+        assert!(Comparison::IncreaseBeyond(5u32).matches(1, 7));
+        assert!(!Comparison::IncreaseBeyond(5u32).matches(1, 6));
+        assert!(!Comparison::IncreaseBeyond(5u32).matches(7, 1));
+        assert!(Comparison::IncreaseBeyond(0.5f64).matches(-1.0, -0.4));
+        assert!(Comparison::Decrease.matches(-1i64, -2));
+    }
+
+    #[test]
+    fn test_parse_comparison() {
+        assert_eq!("increase".parse(), Ok(Comparison::<u32>::Increase));
+        assert_eq!("decrease".parse(), Ok(Comparison::<u32>::Decrease));
+        assert_eq!("unchanged".parse(), Ok(Comparison::<u32>::Unchanged));
+        assert_eq!(
+            "increase-beyond:10".parse(),
+            Ok(Comparison::<u32>::IncreaseBeyond(10))
+        );
+        assert_eq!("increase-beyond:x".parse::<Comparison<u32>>(), Err(()));
+        assert_eq!("sideways".parse::<Comparison<u32>>(), Err(()));
+    }
+}
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+/// A smoothing filter for noisy readings. The smoothed series can be passed
+/// straight back into `count_increases`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// The mean of each `n`-sized window.
+    MovingAverage(usize),
+    /// Each value is `alpha * reading + (1 - alpha) * previous value`,
+    /// starting from the first reading. `alpha` is in `(0, 1]`.
+    Exponential(f64),
+    /// The median of each `n`-sized window.
+    Median(usize),
+    /// The weighted mean of each window, with one weight per position in the
+    /// window (oldest first).
+    Weighted(Vec<f64>),
+}
+
+impl Filter {
+    /// Window filters produce one value per full window, so their output is
+    /// shorter than `input`. The exponential filter produces one value per
+    /// reading.
+    pub fn apply(&self, input: &[f64]) -> Vec<f64> {
+        match self {
+            Filter::MovingAverage(n) => moving_average(input, *n),
+            Filter::Exponential(alpha) => exponential(input, *alpha),
+            Filter::Median(n) => median(input, *n),
+            Filter::Weighted(weights) => weighted(input, weights),
+        }
+    }
+}
+
+/// Re-sums every window rather than keeping a running sum, which would carry
+/// the rounding error from a large reading long after it left the window.
+fn moving_average(input: &[f64], n: usize) -> Vec<f64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    input
+        .windows(n)
+        .map(|window| window.iter().sum::<f64>() / n as f64)
+        .collect()
+}
+
+fn exponential(input: &[f64], alpha: f64) -> Vec<f64> {
+    input
+        .iter()
+        .scan(None, |prev: &mut Option<f64>, &x| {
+            let next = match *prev {
+                Some(p) => alpha * x + (1.0 - alpha) * p,
+                None => x,
+            };
+            *prev = Some(next);
+            Some(next)
+        })
+        .collect()
+}
+
+fn median(input: &[f64], n: usize) -> Vec<f64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    input
+        .windows(n)
+        .map(|window| {
+            let mut sorted = window.to_vec();
+            sorted.sort_unstable_by(f64::total_cmp);
+
+            if n % 2 == 1 {
+                sorted[n / 2]
+            } else {
+                (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+            }
+        })
+        .collect()
+}
+
+fn weighted(input: &[f64], weights: &[f64]) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+
+    if weights.is_empty() || total == 0.0 {
+        return Vec::new();
+    }
+
+    input
+        .windows(weights.len())
+        .map(|window| window.iter().zip(weights).map(|(x, w)| x * w).sum::<f64>() / total)
+        .collect()
+}
+
+/// Parses `sma:<n>`, `ema:<alpha>`, `median:<n>` or `weighted:<w1>,<w2>,...`.
+impl FromStr for Filter {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, arg) = s.split_once(':').ok_or(())?;
+
+        let filter = match kind {
+            "sma" => Filter::MovingAverage(arg.parse().map_err(|_| ())?),
+            "ema" => Filter::Exponential(arg.parse().map_err(|_| ())?),
+            "median" => Filter::Median(arg.parse().map_err(|_| ())?),
+            "weighted" => Filter::Weighted(
+                arg.split(',')
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| ())?,
+            ),
+            _ => return Err(()),
+        };
+
+        match filter {
+            Filter::MovingAverage(0) | Filter::Median(0) => Err(()),
+            Filter::Exponential(alpha) if !(alpha > 0.0 && alpha <= 1.0) => Err(()),
+            _ => Ok(filter),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{count_increases, count_increasing_sums};
+
+    const EXAMPLE: [f64; 10] = [
+        199.0, 200.0, 208.0, 210.0, 200.0, 207.0, 240.0, 269.0, 260.0, 263.0,
+    ];
+
+    #[test]
+    fn test_moving_average() {
+        assert_eq!(
+            Filter::MovingAverage(2).apply(&[1.0, 3.0, 5.0, 4.0]),
+            vec![2.0, 4.0, 4.5]
+        );
+        assert!(Filter::MovingAverage(5).apply(&[1.0, 3.0]).is_empty());
+
+        // A running sum loses the 1.0 added alongside 1e16, and is left at 0
+        // once 1e16 is subtracted again.
+        assert_eq!(
+            Filter::MovingAverage(2).apply(&[1e16, 1.0, 1.0, 1.0]),
+            vec![5e15, 1.0, 1.0]
+        );
+
+        // A 3-reading average rises exactly when the 3-reading sum does.
+        assert_eq!(
+            count_increases(&Filter::MovingAverage(3).apply(&EXAMPLE)),
+            count_increasing_sums(&EXAMPLE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_exponential() {
+        assert_eq!(
+            Filter::Exponential(0.5).apply(&[4.0, 8.0, 0.0]),
+            vec![4.0, 6.0, 3.0]
+        );
+        assert_eq!(Filter::Exponential(1.0).apply(&EXAMPLE), EXAMPLE.to_vec());
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(
+            Filter::Median(3).apply(&[1.0, 9.0, 2.0, 3.0, 100.0, 4.0]),
+            vec![2.0, 3.0, 3.0, 4.0]
+        );
+        assert_eq!(Filter::Median(2).apply(&[1.0, 9.0, 2.0]), vec![5.0, 5.5]);
+    }
+
+    #[test]
+    fn test_weighted() {
+        assert_eq!(
+            Filter::Weighted(vec![1.0, 3.0]).apply(&[4.0, 8.0, 0.0]),
+            vec![7.0, 2.0]
+        );
+        assert_eq!(
+            Filter::Weighted(vec![1.0, 1.0, 1.0]).apply(&EXAMPLE),
+            Filter::MovingAverage(3).apply(&EXAMPLE)
+        );
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!("sma:3".parse(), Ok(Filter::MovingAverage(3)));
+        assert_eq!("ema:0.25".parse(), Ok(Filter::Exponential(0.25)));
+        assert_eq!("median:5".parse(), Ok(Filter::Median(5)));
+        assert_eq!(
+            "weighted:1,2,3".parse(),
+            Ok(Filter::Weighted(vec![1.0, 2.0, 3.0]))
+        );
+        assert_eq!("sma:0".parse::<Filter>(), Err(()));
+        assert_eq!("ema:1.5".parse::<Filter>(), Err(()));
+        assert_eq!("weighted:1,x".parse::<Filter>(), Err(()));
+        assert_eq!("gaussian:3".parse::<Filter>(), Err(()));
+        assert_eq!("sma".parse::<Filter>(), Err(()));
+    }
+}
@@ -0,0 +1,186 @@
+use aoc::arith;
+
+pub use anomaly::{detect_anomalies, Anomaly, AnomalyConfig, AnomalyKind};
+pub use aoc::arith::OverflowError;
+pub use depth::{Comparison, Depth};
+pub use filter::Filter;
+pub use plot::{DepthPlot, Mark};
+pub use report::DepthReport;
+pub use simd::count_increases_simd;
+
+mod anomaly;
+mod depth;
+mod filter;
+mod plot;
+mod report;
+mod simd;
+
+pub fn count_increases<T: Depth>(input: &[T]) -> usize {
+    count_matching(input, Comparison::Increase)
+}
+
+pub fn count_increasing_sums<T: Depth>(input: &[T]) -> Result<usize, OverflowError> {
+    count_increasing_window_sums(input, 3)
+}
+
+pub fn count_increasing_window_sums<T: Depth>(
+    input: &[T],
+    window: usize,
+) -> Result<usize, OverflowError> {
+    count_matching_window_sums(input, window, Comparison::Increase)
+}
+
+/// Counts the values that match `cmp` against the value before them.
+pub fn count_matching<T: Depth>(input: &[T], cmp: Comparison<T>) -> usize {
+    input
+        .windows(2)
+        .filter(|nums| cmp.matches(nums[0], nums[1]))
+        .count()
+}
+
+/// Counts the `window`-sized window sums that match `cmp` against the sum of
+/// the window before them. Integer depths keep a rolling sum rather than
+/// re-summing each window, so this is O(n) for any window size. Float depths
+/// re-sum every window, as a rolling sum would pick up rounding errors from
+/// readings long gone from the window.
+pub fn count_matching_window_sums<T: Depth>(
+    input: &[T],
+    window: usize,
+    cmp: Comparison<T>,
+) -> Result<usize, OverflowError> {
+    if window == 0 || input.len() <= window {
+        return Ok(0);
+    }
+
+    let mut sum = window_sum(&input[..window])?;
+    let mut count = 0;
+
+    for (start, (&oldest, &newest)) in input.iter().zip(&input[window..]).enumerate() {
+        let next = if T::EXACT {
+            arith::add(arith::sub(sum, oldest)?, newest)?
+        } else {
+            window_sum(&input[start + 1..=start + window])?
+        };
+
+        if cmp.matches(sum, next) {
+            count += 1;
+        }
+
+        sum = next;
+    }
+
+    Ok(count)
+}
+
+fn window_sum<T: Depth>(window: &[T]) -> Result<T, OverflowError> {
+    window
+        .iter()
+        .try_fold(T::default(), |acc, &n| arith::add(acc, n))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_count_increases() {
+        assert_eq!(count_increases::<u32>(&[]), 0);
+        assert_eq!(count_increases(&[1]), 0);
+        assert_eq!(count_increases(&[2, 2]), 0);
+        assert_eq!(count_increases(&[2, 1]), 0);
+        assert_eq!(
+            count_increases(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]),
+            7
+        );
+    }
+
+    #[test]
+    fn test_count_increasing_sums() {
+        assert_eq!(count_increasing_sums::<u32>(&[]), Ok(0));
+        assert_eq!(count_increasing_sums(&[1]), Ok(0));
+        assert_eq!(count_increasing_sums(&[1, 2]), Ok(0));
+        assert_eq!(count_increasing_sums(&[1, 2, 3]), Ok(0));
+        assert_eq!(count_increasing_sums(&[1, 2, 3, 0]), Ok(0));
+        assert_eq!(count_increasing_sums(&[1, 2, 3, 1]), Ok(0));
+        assert_eq!(
+            count_increasing_sums(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]),
+            Ok(5)
+        );
+    }
+
+    #[test]
+    fn test_count_increasing_window_sums() {
+        let input = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+        assert_eq!(count_increasing_window_sums(&input, 0), Ok(0));
+        assert_eq!(count_increasing_window_sums(&input, 1), Ok(7));
+        assert_eq!(count_increasing_window_sums(&input, 3), Ok(5));
+        assert_eq!(count_increasing_window_sums(&input, 9), Ok(1));
+        assert_eq!(count_increasing_window_sums(&input, 10), Ok(0));
+        assert_eq!(count_increasing_window_sums(&input, 11), Ok(0));
+
+        for window in 1..=10 {
+            let sums = input
+                .windows(window)
+                .map(|nums| nums.iter().sum())
+                .collect::<Vec<u32>>();
+
+            assert_eq!(
+                count_increasing_window_sums(&input, window),
+                Ok(count_increases(&sums))
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_matching() {
+        let input = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+        assert_eq!(count_matching(&input, Comparison::Increase), 7);
+        assert_eq!(count_matching(&input, Comparison::Decrease), 2);
+        assert_eq!(count_matching(&[1, 1, 2, 2, 2], Comparison::Unchanged), 3);
+        assert_eq!(count_matching(&input, Comparison::IncreaseBeyond(8)), 2);
+
+        assert_eq!(
+            count_matching_window_sums(&input, 3, Comparison::Decrease),
+            Ok(1)
+        );
+        assert_eq!(
+            count_matching_window_sums(&input, 3, Comparison::Unchanged),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_count_generic() {
+        assert_eq!(count_increases(&[-3i64, -2, -5, 0]), 2);
+        assert_eq!(count_increases(&[0.5f64, 0.25, 0.75, 1.0]), 2);
+        assert_eq!(count_increasing_sums(&[-1i32, -2, -3, 10]), Ok(1));
+        assert_eq!(
+            count_matching(&[0.1f64, 0.2, 0.4, 0.5], Comparison::IncreaseBeyond(0.15)),
+            1
+        );
+        assert_eq!(
+            count_increasing_sums(&[i64::MIN, -1, 0, 0]),
+            Err(OverflowError)
+        );
+    }
+
+    #[test]
+    fn test_count_float_window_sums_without_drift() {
+        // Both later windows sum to 0.4, but a rolling sum gets
+        // 0.5 - 0.4 + 0.3 = 0.39999999999999997 for the first of them.
+        assert_eq!(
+            count_matching_window_sums(&[0.4f64, 0.1, 0.3, 0.1], 2, Comparison::Unchanged),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_count_increasing_sums_overflow() {
+        assert_eq!(
+            count_increasing_sums(&[1, u32::MAX, 1, 1]),
+            Err(OverflowError)
+        );
+    }
+}
@@ -1,26 +1,139 @@
-const INPUT: &'static str = include_str!("../input.txt");
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc::timing::timed;
+use aoc01::{
+    count_increases, count_increases_simd, count_increasing_sums, count_matching,
+    count_matching_window_sums, detect_anomalies, AnomalyConfig, Comparison, DepthPlot,
+    DepthReport, Filter, OverflowError,
+};
+
+const INPUT: &str = include_str!("../input.txt");
+
+const USAGE: &str =
+    "Usage: aoc01 [--window <size>] [--compare <mode>] [--filter <filter>] [--report <text|json>] [--anomalies]
+             [--plot] [--svg <path>]
+
+<mode> is one of increase, decrease, unchanged or increase-beyond:<threshold>.
+<filter> is one of sma:<n>, ema:<alpha>, median:<n> or weighted:<w1>,<w2>,...";
+
+fn main() -> Result<(), OverflowError> {
+    let options = Options::parse(env::args().skip(1)).unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    });
 
-fn main() {
     let input = INPUT
         .lines()
         .map(|l| l.parse().unwrap())
         .collect::<Vec<_>>();
 
-    println!("Part 1: {}", count_increases(&input));
-    println!("Part 2: {}", count_increasing_sums(&input));
+    println!(
+        "Part 1: {}",
+        timed("Part 1", || count_increases_simd(&input))
+    );
+    println!(
+        "Part 2: {}",
+        timed("Part 2", || count_increasing_sums(&input))?
+    );
+
+    if let Some(cmp) = options.compare {
+        println!("Matching: {}", count_matching(&input, cmp));
+    }
+
+    if let Some(window) = options.window {
+        let cmp = options.compare.unwrap_or(Comparison::Increase);
+
+        println!(
+            "Window {}: {}",
+            window,
+            count_matching_window_sums(&input, window, cmp)?
+        );
+    }
+
+    if let Some(filter) = options.filter.as_ref() {
+        let readings = input.iter().map(|&n| n as f64).collect::<Vec<_>>();
+        println!("Filtered: {}", count_increases(&filter.apply(&readings)));
+    }
+
+    if options.anomalies {
+        for anomaly in detect_anomalies(&input, &AnomalyConfig::default()) {
+            println!("{}", anomaly);
+        }
+    }
+
+    if options.plot {
+        print!("{}", DepthPlot::new(&input).to_ascii(80, 20));
+    }
+
+    if let Some(path) = options.svg.as_ref() {
+        if let Err(e) = fs::write(path, DepthPlot::new(&input).to_svg()) {
+            eprintln!("Failed to write {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+
+    if let Some(format) = options.report {
+        if let Some(report) = DepthReport::new(&input) {
+            match format {
+                ReportFormat::Text => print!("{}", report),
+                ReportFormat::Json => println!("{}", report.to_json()),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    window: Option<usize>,
+    compare: Option<Comparison<u32>>,
+    filter: Option<Filter>,
+    report: Option<ReportFormat>,
+    anomalies: bool,
+    plot: bool,
+    svg: Option<String>,
 }
 
-fn count_increases(input: &[u32]) -> usize {
-    input.windows(2).filter(|nums| nums[1] > nums[0]).count()
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    Text,
+    Json,
 }
 
-fn count_increasing_sums(input: &[u32]) -> usize {
-    let three_window_sums = input
-        .windows(3)
-        .map(|nums| nums.iter().sum())
-        .collect::<Vec<u32>>();
+impl Options {
+    /// Returns `None` if the arguments are invalid.
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut options = Options::default();
 
-    count_increases(&three_window_sums)
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--anomalies" => {
+                    options.anomalies = true;
+                    continue;
+                }
+                "--plot" => {
+                    options.plot = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            match (arg.as_str(), args.next().as_deref()) {
+                ("--window", Some(size)) => options.window = Some(size.parse().ok()?),
+                ("--compare", Some(mode)) => options.compare = Some(mode.parse().ok()?),
+                ("--filter", Some(filter)) => options.filter = Some(filter.parse().ok()?),
+                ("--report", Some("text")) => options.report = Some(ReportFormat::Text),
+                ("--report", Some("json")) => options.report = Some(ReportFormat::Json),
+                ("--svg", Some(path)) => options.svg = Some(path.to_string()),
+                _ => return None,
+            }
+        }
+
+        Some(options)
+    }
 }
 
 #[cfg(test)]
@@ -28,28 +141,57 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_count_increases() {
-        assert_eq!(count_increases(&[]), 0);
-        assert_eq!(count_increases(&[1]), 0);
-        assert_eq!(count_increases(&[2, 2]), 0);
-        assert_eq!(count_increases(&[2, 1]), 0);
+    fn test_parse_options() {
+        let parse = |a: &[&str]| Options::parse(a.iter().map(|s| s.to_string()));
+
+        assert_eq!(parse(&[]), Some(Options::default()));
         assert_eq!(
-            count_increases(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]),
-            7
+            parse(&["--window", "5", "--report", "json"]),
+            Some(Options {
+                window: Some(5),
+                compare: None,
+                filter: None,
+                report: Some(ReportFormat::Json),
+                anomalies: false,
+                plot: false,
+                svg: None,
+            })
+        );
+        assert_eq!(
+            parse(&["--plot", "--svg", "depths.svg"]),
+            Some(Options {
+                plot: true,
+                svg: Some("depths.svg".to_string()),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse(&["--anomalies", "--window", "2"]),
+            Some(Options {
+                window: Some(2),
+                anomalies: true,
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse(&["--compare", "increase-beyond:3"]),
+            Some(Options {
+                compare: Some(Comparison::IncreaseBeyond(3)),
+                ..Options::default()
+            })
         );
-    }
-
-    #[test]
-    fn test_count_increasing_sums() {
-        assert_eq!(count_increasing_sums(&[]), 0);
-        assert_eq!(count_increasing_sums(&[1]), 0);
-        assert_eq!(count_increasing_sums(&[1, 2]), 0);
-        assert_eq!(count_increasing_sums(&[1, 2, 3]), 0);
-        assert_eq!(count_increasing_sums(&[1, 2, 3, 0]), 0);
-        assert_eq!(count_increasing_sums(&[1, 2, 3, 1]), 0);
         assert_eq!(
-            count_increasing_sums(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]),
-            5
+            parse(&["--filter", "median:5"]),
+            Some(Options {
+                filter: Some(Filter::Median(5)),
+                ..Options::default()
+            })
         );
+        assert_eq!(parse(&["--window"]), None);
+        assert_eq!(parse(&["--window", "x"]), None);
+        assert_eq!(parse(&["--report", "xml"]), None);
+        assert_eq!(parse(&["--compare", "more"]), None);
+        assert_eq!(parse(&["--filter", "sma:0"]), None);
+        assert_eq!(parse(&["--size", "5"]), None);
     }
 }
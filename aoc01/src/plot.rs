@@ -0,0 +1,269 @@
+use std::fmt::Write;
+
+/// A depth profile, with each reading marked by whether it was counted
+/// towards the answers.
+#[derive(Debug)]
+pub struct DepthPlot<'a> {
+    readings: &'a [u32],
+    marks: Vec<Mark>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mark {
+    /// Counted by `count_increases`: deeper than the reading before it.
+    pub increase: bool,
+    /// Counted by `count_increasing_sums`: completes a three-reading window
+    /// whose sum is larger than the window before it.
+    pub sum_increase: bool,
+}
+
+const SVG_WIDTH: f64 = 1000.0;
+const SVG_HEIGHT: f64 = 400.0;
+const SVG_MARGIN: f64 = 20.0;
+
+impl<'a> DepthPlot<'a> {
+    pub fn new(readings: &'a [u32]) -> Self {
+        // Consecutive three-reading windows share two readings, so comparing
+        // their sums is the same as comparing the readings that differ.
+        let marks = (0..readings.len())
+            .map(|i| Mark {
+                increase: i >= 1 && readings[i] > readings[i - 1],
+                sum_increase: i >= 3 && readings[i] > readings[i - 3],
+            })
+            .collect();
+
+        DepthPlot { readings, marks }
+    }
+
+    pub fn marks(&self) -> &[Mark] {
+        &self.marks
+    }
+
+    /// Renders a `width` by `height` character chart, deeper readings lower
+    /// down. When there are more readings than columns each column shows the
+    /// mean of its readings, marked if most of them were counted.
+    pub fn to_ascii(&self, width: usize, height: usize) -> String {
+        let (min, max) = match self.bounds() {
+            Some(bounds) if width > 0 && height > 0 => bounds,
+            _ => return String::new(),
+        };
+
+        let len = self.readings.len();
+        let columns = width.min(len);
+        let mut grid = vec![vec![' '; columns]; height];
+
+        for (column, cells) in (0..columns).map(|c| (c, c * len / columns..(c + 1) * len / columns))
+        {
+            let readings = &self.readings[cells.clone()];
+            let marks = &self.marks[cells];
+
+            let mean = readings.iter().map(|&n| n as f64).sum::<f64>() / readings.len() as f64;
+            let row = scale(mean, min, max, (height - 1) as f64).round() as usize;
+
+            let increases = marks.iter().filter(|m| m.increase).count() * 2 > marks.len();
+            let sum_increases = marks.iter().filter(|m| m.sum_increase).count() * 2 > marks.len();
+
+            grid[row][column] = match (increases, sum_increases) {
+                (true, true) => '*',
+                (true, false) => '+',
+                (false, true) => '^',
+                (false, false) => 'o',
+            };
+        }
+
+        let label_width = max.to_string().len();
+        let mut chart = String::new();
+
+        for (i, row) in grid.iter().enumerate() {
+            let label = match i {
+                0 => min.to_string(),
+                i if i == height - 1 => max.to_string(),
+                _ => String::new(),
+            };
+            let line = row.iter().collect::<String>();
+
+            writeln!(chart, "{:>w$} |{}", label, line.trim_end(), w = label_width).unwrap();
+        }
+
+        writeln!(
+            chart,
+            "{:>w$} +{}",
+            "",
+            "-".repeat(columns),
+            w = label_width
+        )
+        .unwrap();
+        writeln!(
+            chart,
+            "{:>w$}  + increase ({})  ^ window sum increase ({})  * both",
+            "",
+            self.marks.iter().filter(|m| m.increase).count(),
+            self.marks.iter().filter(|m| m.sum_increase).count(),
+            w = label_width
+        )
+        .unwrap();
+
+        chart
+    }
+
+    /// Renders a standalone SVG document with every reading plotted. Readings
+    /// counted by `count_increases` are filled red, and those counted by
+    /// `count_increasing_sums` are ringed in orange.
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+
+        writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = SVG_WIDTH,
+            h = SVG_HEIGHT
+        )
+        .unwrap();
+        writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+        if let Some((min, max)) = self.bounds() {
+            let last = (self.readings.len() - 1).max(1) as f64;
+            let point = |i: usize| {
+                (
+                    SVG_MARGIN + i as f64 / last * (SVG_WIDTH - 2.0 * SVG_MARGIN),
+                    SVG_MARGIN
+                        + scale(
+                            self.readings[i] as f64,
+                            min,
+                            max,
+                            SVG_HEIGHT - 2.0 * SVG_MARGIN,
+                        ),
+                )
+            };
+
+            let points = (0..self.readings.len())
+                .map(|i| {
+                    let (x, y) = point(i);
+                    format!("{:.2},{:.2}", x, y)
+                })
+                .collect::<Vec<_>>();
+
+            writeln!(
+                svg,
+                r#"<polyline fill="none" stroke="steelblue" stroke-width="1" points="{}"/>"#,
+                points.join(" ")
+            )
+            .unwrap();
+
+            for (i, mark) in self.marks.iter().enumerate() {
+                let (x, y) = point(i);
+
+                if mark.sum_increase {
+                    writeln!(
+                        svg,
+                        r#"<circle cx="{:.2}" cy="{:.2}" r="3" fill="none" stroke="orange"/>"#,
+                        x, y
+                    )
+                    .unwrap();
+                }
+                if mark.increase {
+                    writeln!(
+                        svg,
+                        r#"<circle cx="{:.2}" cy="{:.2}" r="1.5" fill="red"/>"#,
+                        x, y
+                    )
+                    .unwrap();
+                }
+            }
+
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="monospace" font-size="12">{} to {}</text>"#,
+                SVG_MARGIN,
+                SVG_MARGIN - 6.0,
+                min,
+                max
+            )
+            .unwrap();
+        }
+
+        writeln!(svg, "</svg>").unwrap();
+
+        svg
+    }
+
+    fn bounds(&self) -> Option<(u32, u32)> {
+        let min = *self.readings.iter().min()?;
+        let max = *self.readings.iter().max()?;
+
+        Some((min, max))
+    }
+}
+
+/// Maps `value` from `min..=max` onto `0..=extent`.
+fn scale(value: f64, min: u32, max: u32, extent: f64) -> f64 {
+    if max == min {
+        0.0
+    } else {
+        (value - min as f64) / (max - min) as f64 * extent
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{count_increases, count_increasing_sums};
+
+    const EXAMPLE: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_marks_match_counts() {
+        let plot = DepthPlot::new(&EXAMPLE);
+
+        assert_eq!(
+            plot.marks().iter().filter(|m| m.increase).count(),
+            count_increases(&EXAMPLE)
+        );
+        assert_eq!(
+            plot.marks().iter().filter(|m| m.sum_increase).count(),
+            count_increasing_sums(&EXAMPLE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        let plot = DepthPlot::new(&EXAMPLE);
+
+        assert_eq!(
+            plot.to_ascii(10, 4),
+            "\
+199 |o++*o+
+    |
+    |      *
+269 |       *^*
+    +----------
+     + increase (7)  ^ window sum increase (5)  * both
+"
+        );
+    }
+
+    #[test]
+    fn test_to_ascii_downsampled() {
+        let plot = DepthPlot::new(&EXAMPLE);
+        let chart = plot.to_ascii(5, 3);
+
+        assert!(chart.lines().take(3).all(|l| l.len() <= "199 |".len() + 5));
+        assert_eq!(chart.lines().count(), 5);
+    }
+
+    #[test]
+    fn test_to_ascii_empty() {
+        assert_eq!(DepthPlot::new(&[]).to_ascii(80, 20), "");
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let svg = DepthPlot::new(&EXAMPLE).to_svg();
+
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(r#"fill="red""#).count(), 7);
+        assert_eq!(svg.matches(r#"stroke="orange""#).count(), 5);
+    }
+}
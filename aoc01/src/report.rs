@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+
+/// Width of each bucket in the histogram of deltas between readings.
+const DELTA_BUCKET_WIDTH: i64 = 10;
+
+/// Descriptive statistics over a series of depth readings. Line numbers are
+/// 1-based, matching the input file.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DepthReport {
+    pub readings: usize,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    pub median: f64,
+    pub longest_increasing_run: Run,
+    pub largest_jump: Option<Jump>,
+    pub delta_histogram: Vec<Bucket>,
+}
+
+/// A run of strictly increasing readings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Run {
+    pub start_line: usize,
+    pub length: usize,
+}
+
+/// The change from the previous reading to the one on `line`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Jump {
+    pub line: usize,
+    pub delta: i64,
+}
+
+/// The number of deltas in `[from, to)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    pub from: i64,
+    pub to: i64,
+    pub count: usize,
+}
+
+impl DepthReport {
+    /// Computes the report in a single pass over `input`. Returns `None` if
+    /// there are no readings.
+    pub fn new(input: &[u32]) -> Option<Self> {
+        let (&first, _) = input.split_first()?;
+
+        let mut min = first;
+        let mut max = first;
+        let mut sum = 0u64;
+        // Counting each distinct value lets us find the median without
+        // sorting a copy of the input.
+        let mut value_counts = BTreeMap::new();
+
+        let mut longest_run = Run {
+            start_line: 1,
+            length: 1,
+        };
+        let mut current_run = longest_run;
+
+        let mut largest_jump: Option<Jump> = None;
+        let mut delta_counts = BTreeMap::new();
+
+        for (i, &n) in input.iter().enumerate() {
+            min = min.min(n);
+            max = max.max(n);
+            sum += n as u64;
+            *value_counts.entry(n).or_insert(0usize) += 1;
+
+            if i == 0 {
+                continue;
+            }
+
+            let line = i + 1;
+            let delta = n as i64 - input[i - 1] as i64;
+
+            if delta > 0 {
+                current_run.length += 1;
+            } else {
+                current_run = Run {
+                    start_line: line,
+                    length: 1,
+                };
+            }
+
+            if current_run.length > longest_run.length {
+                longest_run = current_run;
+            }
+
+            if largest_jump.is_none_or(|j| delta.abs() > j.delta.abs()) {
+                largest_jump = Some(Jump { line, delta });
+            }
+
+            let bucket = delta.div_euclid(DELTA_BUCKET_WIDTH) * DELTA_BUCKET_WIDTH;
+            *delta_counts.entry(bucket).or_insert(0) += 1;
+        }
+
+        Some(DepthReport {
+            readings: input.len(),
+            min,
+            max,
+            mean: sum as f64 / input.len() as f64,
+            median: median(&value_counts, input.len()),
+            longest_increasing_run: longest_run,
+            largest_jump,
+            delta_histogram: delta_counts
+                .into_iter()
+                .map(|(from, count)| Bucket {
+                    from,
+                    to: from + DELTA_BUCKET_WIDTH,
+                    count,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// The median of `len` values, given how many times each value occurs.
+fn median(value_counts: &BTreeMap<u32, usize>, len: usize) -> f64 {
+    // 0-based positions of the middle value(s) in sorted order.
+    let lower = (len - 1) / 2;
+    let upper = len / 2;
+
+    let mut seen = 0;
+    let mut lower_value = None;
+
+    for (&value, &count) in value_counts {
+        seen += count;
+
+        if lower_value.is_none() && seen > lower {
+            lower_value = Some(value);
+        }
+
+        if seen > upper {
+            return (lower_value.unwrap() as f64 + value as f64) / 2.0;
+        }
+    }
+
+    unreachable!("counts must add up to len")
+}
+
+impl fmt::Display for DepthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Readings: {}", self.readings)?;
+        writeln!(f, "Min: {}", self.min)?;
+        writeln!(f, "Max: {}", self.max)?;
+        writeln!(f, "Mean: {:.2}", self.mean)?;
+        writeln!(f, "Median: {}", self.median)?;
+        writeln!(
+            f,
+            "Longest increasing run: {} readings from line {}",
+            self.longest_increasing_run.length, self.longest_increasing_run.start_line
+        )?;
+
+        match self.largest_jump {
+            Some(jump) => writeln!(f, "Largest jump: {:+} at line {}", jump.delta, jump.line)?,
+            None => writeln!(f, "Largest jump: none")?,
+        }
+
+        writeln!(f, "Deltas:")?;
+        for bucket in self.delta_histogram.iter() {
+            writeln!(
+                f,
+                "  [{:>5}, {:>5}) {}",
+                bucket.from, bucket.to, bucket.count
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_depth_report() {
+        let report = DepthReport::new(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]).unwrap();
+
+        assert_eq!(report.readings, 10);
+        assert_eq!(report.min, 199);
+        assert_eq!(report.max, 269);
+        assert_eq!(report.mean, 225.6);
+        assert_eq!(report.median, 209.0);
+        assert_eq!(
+            report.longest_increasing_run,
+            Run {
+                start_line: 1,
+                length: 4
+            }
+        );
+        assert_eq!(report.largest_jump, Some(Jump { line: 7, delta: 33 }));
+        assert_eq!(
+            report.delta_histogram,
+            vec![
+                Bucket {
+                    from: -10,
+                    to: 0,
+                    count: 2
+                },
+                Bucket {
+                    from: 0,
+                    to: 10,
+                    count: 5
+                },
+                Bucket {
+                    from: 20,
+                    to: 30,
+                    count: 1
+                },
+                Bucket {
+                    from: 30,
+                    to: 40,
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_depth_report_edge_cases() {
+        assert_eq!(DepthReport::new(&[]), None);
+
+        let report = DepthReport::new(&[5]).unwrap();
+        assert_eq!(report.median, 5.0);
+        assert_eq!(report.largest_jump, None);
+        assert_eq!(report.delta_histogram, vec![]);
+
+        let report = DepthReport::new(&[4, 1, 3, 2]).unwrap();
+        assert_eq!(report.median, 2.5);
+        assert_eq!(report.largest_jump, Some(Jump { line: 2, delta: -3 }));
+    }
+
+    #[test]
+    fn test_depth_report_json() {
+        let report = DepthReport::new(&[1, 3]).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(json["median"], 2.0);
+        assert_eq!(json["largest_jump"]["delta"], 2);
+        assert_eq!(json["delta_histogram"][0]["count"], 1);
+    }
+}
@@ -0,0 +1,121 @@
+//! A vectorized `count_increases` for `u32` readings. Uses AVX2 when the CPU
+//! supports it, SSE2 on any other x86_64 CPU, and the scalar version elsewhere.
+
+/// Equivalent to `count_increases`, but compares several readings at once.
+pub fn count_increases_simd(input: &[u32]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            // Safety: we've just checked that the CPU supports AVX2.
+            return unsafe { x86_64::count_increases_avx2(input) };
+        }
+
+        // Safety: SSE2 is part of the x86_64 baseline.
+        unsafe { x86_64::count_increases_sse2(input) }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    count_increases_scalar(input)
+}
+
+fn count_increases_scalar(input: &[u32]) -> usize {
+    input.windows(2).filter(|nums| nums[1] > nums[0]).count()
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use std::arch::x86_64::*;
+
+    use super::count_increases_scalar;
+
+    // There are only signed 32-bit comparisons, so both sides are shifted
+    // into the signed range by flipping their top bit first. This preserves
+    // their unsigned order.
+    const SIGN_BIT: i32 = i32::MIN;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn count_increases_avx2(input: &[u32]) -> usize {
+        const LANES: usize = 8;
+
+        let sign = _mm256_set1_epi32(SIGN_BIT);
+        let mut count = 0;
+        let mut i = 0;
+
+        // Each step compares input[i + 1..i + 9] against input[i..i + 8].
+        while i + LANES < input.len() {
+            let prev = _mm256_loadu_si256(input.as_ptr().add(i) as *const __m256i);
+            let next = _mm256_loadu_si256(input.as_ptr().add(i + 1) as *const __m256i);
+
+            let greater =
+                _mm256_cmpgt_epi32(_mm256_xor_si256(next, sign), _mm256_xor_si256(prev, sign));
+            count += _mm256_movemask_ps(_mm256_castsi256_ps(greater)).count_ones() as usize;
+
+            i += LANES;
+        }
+
+        count + count_increases_scalar(&input[i..])
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn count_increases_sse2(input: &[u32]) -> usize {
+        const LANES: usize = 4;
+
+        let sign = _mm_set1_epi32(SIGN_BIT);
+        let mut count = 0;
+        let mut i = 0;
+
+        // Each step compares input[i + 1..i + 5] against input[i..i + 4].
+        while i + LANES < input.len() {
+            let prev = _mm_loadu_si128(input.as_ptr().add(i) as *const __m128i);
+            let next = _mm_loadu_si128(input.as_ptr().add(i + 1) as *const __m128i);
+
+            let greater = _mm_cmpgt_epi32(_mm_xor_si128(next, sign), _mm_xor_si128(prev, sign));
+            count += _mm_movemask_ps(_mm_castsi128_ps(greater)).count_ones() as usize;
+
+            i += LANES;
+        }
+
+        count + count_increases_scalar(&input[i..])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::count_increases;
+
+    #[test]
+    fn test_count_increases_simd() {
+        assert_eq!(count_increases_simd(&[]), 0);
+        assert_eq!(count_increases_simd(&[1]), 0);
+        assert_eq!(
+            count_increases_simd(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]),
+            7
+        );
+        assert_eq!(count_increases_simd(&[0, u32::MAX, 0, 1 << 31, 1 << 30]), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_count_increases(input in prop::collection::vec(any::<u32>(), 0..500)) {
+            prop_assert_eq!(count_increases_simd(&input), count_increases(&input));
+        }
+
+        #[test]
+        fn prop_matches_count_increases_small_values(
+            input in prop::collection::vec(0u32..4, 0..500)
+        ) {
+            prop_assert_eq!(count_increases_simd(&input), count_increases(&input));
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        #[test]
+        fn prop_sse2_matches_count_increases(input in prop::collection::vec(any::<u32>(), 0..500)) {
+            // Safety: SSE2 is part of the x86_64 baseline.
+            let count = unsafe { x86_64::count_increases_sse2(&input) };
+            prop_assert_eq!(count, count_increases(&input));
+        }
+    }
+}
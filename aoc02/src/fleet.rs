@@ -0,0 +1,372 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Movement, NavigationError, Position, Step, Submarine};
+
+/// Several submarines following their own command logs, advanced together
+/// one movement at a time.
+pub struct Fleet {
+    vessels: Vec<Vessel>,
+    tick: usize,
+}
+
+struct Vessel {
+    id: String,
+    sub: Submarine,
+    moves: Vec<Movement>,
+}
+
+/// The closest two submarines came after the same tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Approach {
+    pub a: String,
+    pub b: String,
+    pub distance: f64,
+    pub tick: usize,
+}
+
+/// Two submarines ending a tick at the same position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Collision {
+    pub a: String,
+    pub b: String,
+    pub tick: usize,
+    pub pos: Position,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetReport {
+    /// Each submarine's final position, in the order they first appear in
+    /// the log.
+    pub final_positions: Vec<(String, Position)>,
+    /// One entry per pair of submarines.
+    pub closest_approaches: Vec<Approach>,
+    pub collisions: Vec<Collision>,
+}
+
+/// Returned when a line of a fleet log isn't a submarine id followed by a
+/// movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FleetLogError {
+    /// 1-based, counting blank lines.
+    pub line: usize,
+}
+
+impl fmt::Display for FleetLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected a submarine id and a movement, e.g. `alpha forward 5`",
+            self.line
+        )
+    }
+}
+
+impl Error for FleetLogError {}
+
+impl Fleet {
+    /// Reads a log where every line is a submarine id followed by one of its
+    /// movements, e.g. `alpha forward 5`. Each submarine's movements are
+    /// taken in the order they appear. Blank lines are skipped.
+    pub fn from_log(log: &str) -> Result<Self, FleetLogError> {
+        let mut vessels: Vec<Vessel> = Vec::new();
+
+        for (i, line) in log.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (id, movement) = line
+                .split_once(' ')
+                .and_then(|(id, movement)| Some((id, movement.parse::<Movement>().ok()?)))
+                .ok_or(FleetLogError { line: i + 1 })?;
+
+            match vessels.iter_mut().find(|v| v.id == id) {
+                Some(vessel) => vessel.moves.push(movement),
+                None => vessels.push(Vessel {
+                    id: id.to_string(),
+                    sub: Submarine::new(),
+                    moves: vec![movement],
+                }),
+            }
+        }
+
+        Ok(Fleet { vessels, tick: 0 })
+    }
+
+    /// How many times the fleet has stepped.
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (&str, &Position)> {
+        self.vessels.iter().map(|v| (v.id.as_str(), &v.sub.pos))
+    }
+
+    /// Moves every submarine that still has movements left by one movement.
+    /// Returns `false`, without stepping, once every log is exhausted. If any
+    /// submarine can't move, none of them do.
+    pub fn step(&mut self) -> Result<bool, NavigationError> {
+        let tick = self.tick;
+        let mut moved: Vec<(usize, Step)> = Vec::new();
+
+        for (i, vessel) in self.vessels.iter_mut().enumerate() {
+            if let Some(movement) = vessel.moves.get(tick) {
+                let before = Step {
+                    pos: vessel.sub.pos,
+                    aim: vessel.sub.aim,
+                };
+
+                if let Err(e) = vessel.sub.traverse(movement) {
+                    for &(j, before) in &moved {
+                        self.vessels[j].sub.undo(before);
+                    }
+
+                    return Err(e);
+                }
+
+                moved.push((i, before));
+            }
+        }
+
+        if !moved.is_empty() {
+            self.tick += 1;
+        }
+
+        Ok(!moved.is_empty())
+    }
+
+    /// Steps the fleet until every log is exhausted, tracking how close each
+    /// pair of submarines gets. They all launch from the same point, so
+    /// distances are only compared after each step.
+    pub fn run(mut self) -> Result<FleetReport, NavigationError> {
+        let mut closest_approaches: Vec<Approach> = Vec::new();
+        let mut collisions = Vec::new();
+
+        while self.step()? {
+            for (i, (a, b)) in self.pairs().enumerate() {
+                let distance = a.sub.pos.distance_to(&b.sub.pos);
+
+                if distance == 0.0 {
+                    collisions.push(Collision {
+                        a: a.id.clone(),
+                        b: b.id.clone(),
+                        tick: self.tick,
+                        pos: a.sub.pos,
+                    });
+                }
+
+                match closest_approaches.get_mut(i) {
+                    Some(closest) if distance < closest.distance => {
+                        closest.distance = distance;
+                        closest.tick = self.tick;
+                    }
+                    Some(_) => {}
+                    None => closest_approaches.push(Approach {
+                        a: a.id.clone(),
+                        b: b.id.clone(),
+                        distance,
+                        tick: self.tick,
+                    }),
+                }
+            }
+        }
+
+        Ok(FleetReport {
+            final_positions: self
+                .positions()
+                .map(|(id, pos)| (id.to_string(), *pos))
+                .collect(),
+            closest_approaches,
+            collisions,
+        })
+    }
+
+    fn pairs(&self) -> impl Iterator<Item = (&Vessel, &Vessel)> {
+        self.vessels
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| self.vessels[i + 1..].iter().map(move |b| (a, b)))
+    }
+}
+
+impl fmt::Display for FleetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Final positions:")?;
+        for (id, pos) in &self.final_positions {
+            writeln!(f, "  {}: {}", id, pos)?;
+        }
+
+        writeln!(f, "Closest approaches:")?;
+        for approach in &self.closest_approaches {
+            writeln!(
+                f,
+                "  {} and {}: {:.1} after tick {}",
+                approach.a, approach.b, approach.distance, approach.tick
+            )?;
+        }
+
+        writeln!(f, "Collisions: {}", self.collisions.len())?;
+        for collision in &self.collisions {
+            writeln!(
+                f,
+                "  {} and {} after tick {}",
+                collision.a, collision.b, collision.tick
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Limits, Violation};
+
+    const LOG: &str = "
+        alpha forward 5
+        bravo down 1
+        alpha down 5
+        bravo forward 5
+        charlie forward 1
+        alpha forward 2
+        bravo forward 5
+    ";
+
+    #[test]
+    fn test_from_log() {
+        let fleet = Fleet::from_log(LOG).unwrap();
+
+        assert_eq!(
+            fleet.positions().map(|(id, _)| id).collect::<Vec<_>>(),
+            ["alpha", "bravo", "charlie"]
+        );
+        assert_eq!(fleet.vessels[0].moves.len(), 3);
+        assert_eq!(fleet.vessels[2].moves, [Movement::Forward(1)]);
+    }
+
+    #[test]
+    fn test_from_log_errors() {
+        let error = |log| Fleet::from_log(log).err();
+
+        assert_eq!(
+            error("alpha forward 5\n\nalpha"),
+            Some(FleetLogError { line: 3 })
+        );
+        assert_eq!(error("alpha sideways 5"), Some(FleetLogError { line: 1 }));
+        assert_eq!(error("alpha forward -5"), Some(FleetLogError { line: 1 }));
+        assert_eq!(
+            FleetLogError { line: 3 }.to_string(),
+            "line 3: expected a submarine id and a movement, e.g. `alpha forward 5`"
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let mut fleet = Fleet::from_log(LOG).unwrap();
+
+        assert_eq!(fleet.step(), Ok(true));
+        assert_eq!(fleet.tick(), 1);
+        assert_eq!(
+            fleet
+                .positions()
+                .map(|(_, pos)| pos.horizontal)
+                .collect::<Vec<_>>(),
+            [5, 0, 1]
+        );
+
+        assert_eq!(fleet.step(), Ok(true));
+        assert_eq!(fleet.step(), Ok(true));
+        assert_eq!(fleet.step(), Ok(false));
+        assert_eq!(fleet.tick(), 3);
+    }
+
+    #[test]
+    fn test_step_is_all_or_nothing() {
+        let mut fleet = Fleet::from_log(LOG).unwrap();
+        fleet.vessels[1].sub.set_limits(Limits {
+            max_aim: Some(0),
+            ..Limits::default()
+        });
+
+        // bravo can't aim down, so alpha doesn't move forward either.
+        let error = fleet.step().unwrap_err();
+        assert_eq!(error.violation, Violation::AimTooSteep { aim: 1, max: 0 });
+        assert_eq!(fleet.tick(), 0);
+        assert!(fleet.positions().all(|(_, pos)| *pos == Position::start()));
+        assert!(fleet.vessels.iter().all(|v| v.sub.steps() == 0));
+    }
+
+    #[test]
+    fn test_run() {
+        let report = Fleet::from_log(LOG).unwrap().run().unwrap();
+
+        assert_eq!(
+            report.final_positions,
+            [
+                (
+                    "alpha".to_string(),
+                    Position {
+                        horizontal: 7,
+                        vertical: 10,
+                        lateral: None
+                    }
+                ),
+                (
+                    "bravo".to_string(),
+                    Position {
+                        horizontal: 10,
+                        vertical: 10,
+                        lateral: None
+                    }
+                ),
+                (
+                    "charlie".to_string(),
+                    Position {
+                        horizontal: 1,
+                        vertical: 0,
+                        lateral: None
+                    }
+                ),
+            ]
+        );
+
+        // alpha and bravo are (5, 0) and (0, 0) after tick 1, then (5, 0) and
+        // (5, 5), then (7, 10) and (10, 10).
+        let alpha_bravo = &report.closest_approaches[0];
+        assert_eq!(
+            (alpha_bravo.a.as_str(), alpha_bravo.b.as_str()),
+            ("alpha", "bravo")
+        );
+        assert_eq!(alpha_bravo.distance, 3.0);
+        assert_eq!(alpha_bravo.tick, 3);
+        assert_eq!(report.closest_approaches.len(), 3);
+
+        assert_eq!(report.collisions, []);
+    }
+
+    #[test]
+    fn test_collision() {
+        let report = Fleet::from_log("a forward 2\nb forward 1\nb forward 1\na up 1")
+            .unwrap()
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            report.collisions,
+            [Collision {
+                a: "a".to_string(),
+                b: "b".to_string(),
+                tick: 2,
+                pos: Position {
+                    horizontal: 2,
+                    vertical: 0,
+                    lateral: None
+                },
+            }]
+        );
+        assert_eq!(report.closest_approaches[0].distance, 0.0);
+    }
+}
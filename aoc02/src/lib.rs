@@ -0,0 +1,20 @@
+pub use aoc::arith::OverflowError;
+pub use fleet::{Approach, Collision, Fleet, FleetLogError, FleetReport};
+pub use limits::{Hazard, Limits, NavigationError, Violation};
+pub use movement::{Movement, ParseMovementError};
+pub use navigation::{Aimed, Direct, NavigationModel};
+pub use repl::{run, Repl};
+pub use route::route_to;
+pub use script::{load_script, Script, ScriptError, Statement};
+pub use submarine::{Position, Submarine};
+pub use trajectory::{Step, Trajectory};
+
+mod fleet;
+mod limits;
+mod movement;
+mod navigation;
+mod repl;
+mod route;
+mod script;
+mod submarine;
+mod trajectory;
@@ -0,0 +1,288 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::{OverflowError, Position};
+
+/// Operating limits a `Submarine` refuses to break. The default imposes none.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The deepest the submarine may go.
+    pub max_depth: Option<i64>,
+    /// Whether the submarine must stay at or below the surface (depth 0).
+    pub no_surfacing: bool,
+    /// The steepest aim allowed, up or down.
+    pub max_aim: Option<i64>,
+    /// Regions the submarine must not pass through.
+    pub hazards: Vec<Hazard>,
+}
+
+/// A forbidden rectangle of horizontal positions and depths, edges included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hazard {
+    pub horizontal: RangeInclusive<i64>,
+    pub depth: RangeInclusive<i64>,
+}
+
+/// Returned when a movement would break the submarine's limits, or overflow.
+/// The submarine stays where it was before the movement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationError {
+    /// The index of the offending movement, counting every movement since
+    /// the submarine was launched.
+    pub step: usize,
+    pub violation: Violation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    Overflow,
+    TooDeep {
+        depth: i64,
+        max: i64,
+    },
+    Surfaced {
+        depth: i64,
+    },
+    AimTooSteep {
+        aim: i64,
+        max: i64,
+    },
+    /// The movement passed through `Limits::hazards[index]`.
+    Hazard {
+        index: usize,
+    },
+}
+
+impl Limits {
+    /// Checks a movement from `from` that ends at `to` with the given aim.
+    /// Depth changes linearly during a movement, so only its end needs
+    /// checking against the depth limits, but hazards are checked along the
+    /// whole path.
+    pub(crate) fn check(&self, from: &Position, to: &Position, aim: i64) -> Result<(), Violation> {
+        if let Some(max) = self.max_depth.filter(|&max| to.vertical > max) {
+            return Err(Violation::TooDeep {
+                depth: to.vertical,
+                max,
+            });
+        }
+
+        if self.no_surfacing && to.vertical < 0 {
+            return Err(Violation::Surfaced { depth: to.vertical });
+        }
+
+        if let Some(max) = self
+            .max_aim
+            .filter(|&max| aim.unsigned_abs() > max.unsigned_abs())
+        {
+            return Err(Violation::AimTooSteep { aim, max });
+        }
+
+        if let Some(index) = self.hazards.iter().position(|h| h.crosses(from, to)) {
+            return Err(Violation::Hazard { index });
+        }
+
+        Ok(())
+    }
+}
+
+impl Hazard {
+    /// Whether the straight line from `from` to `to` touches the hazard,
+    /// clipping the line against each axis in turn (Liang–Barsky).
+    fn crosses(&self, from: &Position, to: &Position) -> bool {
+        let mut enter = 0.0f64;
+        let mut exit = 1.0f64;
+
+        for (start, end, range) in [
+            (from.horizontal, to.horizontal, &self.horizontal),
+            (from.vertical, to.vertical, &self.depth),
+        ] {
+            let (start, delta) = (start as f64, end as f64 - start as f64);
+            let (low, high) = (*range.start() as f64, *range.end() as f64);
+
+            if delta == 0.0 {
+                if start < low || start > high {
+                    return false;
+                }
+            } else {
+                let (a, b) = ((low - start) / delta, (high - start) / delta);
+
+                enter = enter.max(a.min(b));
+                exit = exit.min(a.max(b));
+
+                if enter > exit {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl From<OverflowError> for Violation {
+    fn from(_: OverflowError) -> Self {
+        Violation::Overflow
+    }
+}
+
+impl fmt::Display for NavigationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}: ", self.step)?;
+
+        match self.violation {
+            Violation::Overflow => write!(f, "{}", OverflowError),
+            Violation::TooDeep { depth, max } => {
+                write!(f, "depth {} is deeper than the limit of {}", depth, max)
+            }
+            Violation::Surfaced { depth } => write!(f, "depth {} is above the surface", depth),
+            Violation::AimTooSteep { aim, max } => {
+                write!(f, "aim {} is steeper than the limit of {}", aim, max)
+            }
+            Violation::Hazard { index } => write!(f, "passed through hazard {}", index),
+        }
+    }
+}
+
+impl Error for NavigationError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Aimed, Direct, Movement, Submarine};
+
+    fn hazard(horizontal: RangeInclusive<i64>, depth: RangeInclusive<i64>) -> Hazard {
+        Hazard { horizontal, depth }
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut sub = Submarine::<Aimed>::new();
+        sub.set_limits(Limits {
+            max_depth: Some(20),
+            ..Limits::default()
+        });
+
+        sub.traverse_multiple(&[Movement::Down(2), Movement::Forward(10)])
+            .unwrap();
+        assert_eq!(
+            sub.traverse(&Movement::Forward(1)),
+            Err(NavigationError {
+                step: 2,
+                violation: Violation::TooDeep { depth: 22, max: 20 }
+            })
+        );
+        assert_eq!(sub.pos.vertical, 20);
+        assert_eq!(sub.steps(), 2);
+    }
+
+    #[test]
+    fn test_no_surfacing() {
+        let mut sub = Submarine::<Direct>::new();
+        sub.set_limits(Limits {
+            no_surfacing: true,
+            ..Limits::default()
+        });
+
+        assert_eq!(
+            sub.traverse_multiple(&[Movement::Down(3), Movement::Up(3), Movement::Up(1)]),
+            Err(NavigationError {
+                step: 2,
+                violation: Violation::Surfaced { depth: -1 }
+            })
+        );
+        assert_eq!(sub.pos.vertical, 0);
+    }
+
+    #[test]
+    fn test_max_aim() {
+        let mut sub = Submarine::<Aimed>::new();
+        sub.set_limits(Limits {
+            max_aim: Some(5),
+            ..Limits::default()
+        });
+
+        sub.traverse_multiple(&[Movement::Down(5), Movement::Up(10)])
+            .unwrap();
+        assert_eq!(
+            sub.traverse(&Movement::Up(1)),
+            Err(NavigationError {
+                step: 2,
+                violation: Violation::AimTooSteep { aim: -6, max: 5 }
+            })
+        );
+        assert_eq!(sub.aim, -5);
+    }
+
+    #[test]
+    fn test_hazards() {
+        let limits = Limits {
+            hazards: vec![hazard(0..=2, 50..=60), hazard(10..=14, 15..=18)],
+            ..Limits::default()
+        };
+
+        // Passes over the first hazard, then dives through the second one
+        // without stopping in it: (0, 0) -> (5, 0) -> (20, 30).
+        let mut sub = Submarine::<Aimed>::new();
+        sub.set_limits(limits.clone());
+        assert_eq!(
+            sub.traverse_multiple(&[
+                Movement::Forward(5),
+                Movement::Down(2),
+                Movement::Forward(15)
+            ]),
+            Err(NavigationError {
+                step: 2,
+                violation: Violation::Hazard { index: 1 }
+            })
+        );
+
+        // Ends just beside it instead: (0, 0) -> (9, 0) -> (9, 18).
+        let mut sub = Submarine::<Direct>::new();
+        sub.set_limits(limits);
+        sub.traverse_multiple(&[Movement::Forward(9), Movement::Down(18)])
+            .unwrap();
+        assert_eq!(
+            sub.traverse(&Movement::Forward(1)),
+            Err(NavigationError {
+                step: 2,
+                violation: Violation::Hazard { index: 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_hazard_crosses() {
+        let hazard = hazard(10..=12, 15..=18);
+        let at = |horizontal, vertical| Position {
+            horizontal,
+            vertical,
+            lateral: None,
+        };
+
+        assert!(hazard.crosses(&at(0, 0), &at(20, 30)));
+        assert!(hazard.crosses(&at(11, 16), &at(11, 16)));
+        assert!(hazard.crosses(&at(12, 0), &at(12, 15)));
+        assert!(!hazard.crosses(&at(0, 0), &at(20, 10)));
+        assert!(!hazard.crosses(&at(13, 0), &at(13, 100)));
+        assert!(!hazard.crosses(&at(0, 14), &at(9, 18)));
+    }
+
+    #[test]
+    fn test_display() {
+        let error = NavigationError {
+            step: 7,
+            violation: Violation::TooDeep { depth: 22, max: 20 },
+        };
+        assert_eq!(
+            error.to_string(),
+            "step 7: depth 22 is deeper than the limit of 20"
+        );
+
+        let error = NavigationError {
+            step: 0,
+            violation: Violation::Overflow,
+        };
+        assert_eq!(error.to_string(), "step 0: arithmetic overflow");
+    }
+}
@@ -1,83 +1,177 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::process;
+
+use aoc::timing::timed;
+use aoc02::{
+    load_script, route_to, run, Aimed, Direct, Fleet, Hazard, Limits, Movement, NavigationModel,
+    Position, Submarine,
+};
+
 const INPUT: &str = include_str!("../input.txt");
 
-fn main() {
-    let input = INPUT.lines().map(Movement::from).collect::<Vec<_>>();
+const USAGE: &str = "Usage: aoc02 [--script <path>] [--trajectory <csv-path>] [<limits>]
+       aoc02 --route-to <horizontal>,<depth>[,<lateral>]
+       aoc02 --fleet <log-path>
+       aoc02 repl [<limits>]
 
-    let mut sub = Submarine::new();
-    sub.traverse_multiple(&input);
+<limits> are any of --max-depth <depth>, --no-surfacing, --max-aim <aim> and
+--hazard <from>..<to>,<top>..<bottom>, which can be repeated.
 
-    // Part 1's solution was obsoleted by Part 2 :(
+Without --script, the puzzle input is used. Each line of a fleet log is a
+submarine id followed by a movement, e.g. `alpha forward 5`.";
 
-    println!("Part 2: {}", sub.pos.horizontal * sub.pos.vertical);
-}
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse(env::args().skip(1)).unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    });
 
-struct Submarine {
-    pos: Position,
-    aim: i32,
-}
+    if options.repl {
+        return Ok(run(io::stdin().lock(), io::stdout(), options.limits)?);
+    }
 
-impl Submarine {
-    fn new() -> Self {
-        Submarine {
-            pos: Position::start(),
-            aim: 0,
+    if let Some(target) = options.route_to {
+        for movement in route_to(&target) {
+            println!("{}", movement);
         }
+
+        return Ok(());
+    }
+
+    if let Some(path) = options.fleet.as_ref() {
+        let log = fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read {}: {}", path, e);
+            process::exit(1);
+        });
+        let fleet = Fleet::from_log(&log).unwrap_or_else(|e| {
+            eprintln!("Invalid fleet log {}: {}", path, e);
+            process::exit(1);
+        });
+        print!("{}", fleet.run()?);
+
+        return Ok(());
     }
 
-    fn traverse_multiple(&mut self, moves: &[Movement]) {
-        for m in moves {
-            self.traverse(m);
+    let input = match options.script.as_ref() {
+        Some(path) => load_script(path).unwrap_or_else(|e| {
+            eprintln!("Failed to load {}: {}", path, e);
+            process::exit(1);
+        }),
+        None => INPUT.lines().map(Movement::from).collect::<Vec<_>>(),
+    };
+
+    let mut sub = Submarine::<Direct>::new();
+    sub.set_limits(options.limits.clone());
+    navigate("Part 1", &mut sub, &input);
+    println!("Part 1: {}", sub.pos.product()?);
+
+    let mut sub = Submarine::<Aimed>::new();
+    sub.set_limits(options.limits.clone());
+    if options.trajectory.is_some() {
+        sub.start_recording();
+    }
+    navigate("Part 2", &mut sub, &input);
+    println!("Part 2: {}", sub.pos.product()?);
+
+    if let (Some(path), Some(trajectory)) = (options.trajectory, sub.trajectory()) {
+        println!("Max depth: {}", trajectory.max_depth());
+        println!("Total distance: {:.1}", trajectory.total_distance());
+
+        if let Err(e) = fs::write(&path, trajectory.to_csv()) {
+            eprintln!("Failed to write {}: {}", path, e);
+            process::exit(1);
         }
     }
 
-    fn traverse(&mut self, movement: &Movement) {
-        match movement {
-            Movement::Down(n) => self.aim += *n as i32,
-            Movement::Up(n) => self.aim -= *n as i32,
-            Movement::Forward(n) => {
-                self.pos.horizontal += n;
-                self.pos.vertical += (self.aim * *n as i32) as u32;
-            }
-        };
+    Ok(())
+}
+
+/// Runs `sub` through `input`, exiting if it can't complete the course.
+fn navigate<N: NavigationModel>(label: &str, sub: &mut Submarine<N>, input: &[Movement]) {
+    if let Err(e) = timed(label, || sub.traverse_multiple(input)) {
+        eprintln!("{} failed at {}", label, e);
+        process::exit(1);
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-struct Position {
-    horizontal: u32,
-    vertical: u32,
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Options {
+    script: Option<String>,
+    trajectory: Option<String>,
+    route_to: Option<Position>,
+    fleet: Option<String>,
+    limits: Limits,
+    repl: bool,
 }
 
-impl Position {
-    fn start() -> Self {
-        Position {
-            horizontal: 0,
-            vertical: 0,
+impl Options {
+    /// Returns `None` if the arguments are invalid.
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "repl" => {
+                    options.repl = true;
+                    continue;
+                }
+                "--no-surfacing" => {
+                    options.limits.no_surfacing = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            match (arg.as_str(), args.next()) {
+                ("--script", Some(path)) => options.script = Some(path),
+                ("--trajectory", Some(path)) => options.trajectory = Some(path),
+                ("--fleet", Some(path)) => options.fleet = Some(path),
+                ("--max-depth", Some(n)) => options.limits.max_depth = Some(n.parse().ok()?),
+                ("--max-aim", Some(n)) => options.limits.max_aim = Some(n.parse().ok()?),
+                ("--hazard", Some(h)) => options.limits.hazards.push(parse_hazard(&h)?),
+                ("--route-to", Some(target)) => options.route_to = Some(parse_target(&target)?),
+                _ => return None,
+            }
         }
+
+        Some(options)
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Movement {
-    Forward(u32),
-    Down(u32),
-    Up(u32),
+fn parse_hazard(s: &str) -> Option<Hazard> {
+    let parse_range = |r: &str| {
+        let (start, end) = r.split_once("..")?;
+        Some(start.parse().ok()?..=end.parse().ok()?)
+    };
+    let (horizontal, depth) = s.split_once(',')?;
+
+    Some(Hazard {
+        horizontal: parse_range(horizontal)?,
+        depth: parse_range(depth)?,
+    })
 }
 
-impl From<&str> for Movement {
-    fn from(input: &str) -> Self {
-        let parts: [_; 2] = input
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
-
-        match &parts {
-            ["forward", n] => Movement::Forward(n.parse().unwrap()),
-            ["down", n] => Movement::Down(n.parse().unwrap()),
-            ["up", n] => Movement::Up(n.parse().unwrap()),
-            _ => unreachable!(),
-        }
+fn parse_target(s: &str) -> Option<Position> {
+    let coords = s
+        .split(',')
+        .map(|n| n.parse().ok())
+        .collect::<Option<Vec<i64>>>()?;
+
+    match coords[..] {
+        [horizontal, vertical] => Some(Position {
+            horizontal,
+            vertical,
+            lateral: None,
+        }),
+        [horizontal, vertical, lateral] => Some(Position {
+            horizontal,
+            vertical,
+            lateral: Some(lateral),
+        }),
+        _ => None,
     }
 }
 
@@ -86,33 +180,63 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_movement_parsing() {
-        assert_eq!(Movement::from("forward 8"), Movement::Forward(8));
-        assert_eq!(Movement::from("down 8"), Movement::Down(8));
-        assert_eq!(Movement::from("up 8"), Movement::Up(8));
-    }
+    fn test_parse_options() {
+        let parse = |a: &[&str]| Options::parse(a.iter().map(|s| s.to_string()));
 
-    #[test]
-    fn test_traverse() {
-        let mut sub = Submarine::new();
-
-        sub.traverse_multiple(&[]);
-        assert_eq!(sub.pos, Position::start());
-
-        sub.traverse_multiple(&[
-            Movement::Forward(5),
-            Movement::Down(5),
-            Movement::Forward(8),
-            Movement::Up(3),
-            Movement::Down(8),
-            Movement::Forward(2),
-        ]);
+        assert_eq!(parse(&[]), Some(Options::default()));
         assert_eq!(
-            sub.pos,
-            Position {
-                horizontal: 15,
-                vertical: 60
-            }
+            parse(&["--trajectory", "path.csv", "--script", "dive.sub"]),
+            Some(Options {
+                script: Some("dive.sub".to_string()),
+                trajectory: Some("path.csv".to_string()),
+                route_to: None,
+                fleet: None,
+                limits: Limits::default(),
+                repl: false,
+            })
+        );
+        assert_eq!(
+            parse(&["repl"]),
+            Some(Options {
+                repl: true,
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "--max-depth",
+                "900",
+                "--no-surfacing",
+                "--hazard",
+                "10..20,-5..5"
+            ]),
+            Some(Options {
+                limits: Limits {
+                    max_depth: Some(900),
+                    no_surfacing: true,
+                    max_aim: None,
+                    hazards: vec![Hazard {
+                        horizontal: 10..=20,
+                        depth: -5..=5,
+                    }],
+                },
+                ..Options::default()
+            })
+        );
+        assert_eq!(parse(&["--hazard", "10..20"]), None);
+        assert_eq!(
+            parse(&["--route-to", "15,-60,2"]),
+            Some(Options {
+                route_to: Some(Position {
+                    horizontal: 15,
+                    vertical: -60,
+                    lateral: Some(2),
+                }),
+                ..Options::default()
+            })
         );
+        assert_eq!(parse(&["--route-to", "15"]), None);
+        assert_eq!(parse(&["--script"]), None);
+        assert_eq!(parse(&["--speed", "5"]), None);
     }
 }
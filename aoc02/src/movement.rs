@@ -0,0 +1,99 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Movement {
+    Forward(u32),
+    Down(u32),
+    Up(u32),
+    Back(u32),
+    Left(u32),
+    Right(u32),
+}
+
+/// Panics if `input` isn't a valid movement. Use `str::parse` to handle
+/// invalid input.
+impl From<&str> for Movement {
+    fn from(input: &str) -> Self {
+        input.parse().unwrap()
+    }
+}
+
+impl FromStr for Movement {
+    type Err = ParseMovementError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parts: [_; 2] = input
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| ParseMovementError)?;
+        let n = parts[1].parse().map_err(|_| ParseMovementError)?;
+
+        match parts[0] {
+            "forward" => Ok(Movement::Forward(n)),
+            "down" => Ok(Movement::Down(n)),
+            "up" => Ok(Movement::Up(n)),
+            "back" => Ok(Movement::Back(n)),
+            "left" => Ok(Movement::Left(n)),
+            "right" => Ok(Movement::Right(n)),
+            _ => Err(ParseMovementError),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseMovementError;
+
+impl fmt::Display for ParseMovementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a direction and a distance, e.g. `forward 5`")
+    }
+}
+
+impl Error for ParseMovementError {}
+
+/// Formats the movement the way `From<&str>` reads it, e.g. `forward 5`.
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Movement::Forward(n) => write!(f, "forward {}", n),
+            Movement::Down(n) => write!(f, "down {}", n),
+            Movement::Up(n) => write!(f, "up {}", n),
+            Movement::Back(n) => write!(f, "back {}", n),
+            Movement::Left(n) => write!(f, "left {}", n),
+            Movement::Right(n) => write!(f, "right {}", n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_movement_parsing() {
+        assert_eq!(Movement::from("forward 8"), Movement::Forward(8));
+        assert_eq!(Movement::from("down 8"), Movement::Down(8));
+        assert_eq!(Movement::from("up 8"), Movement::Up(8));
+        assert_eq!(Movement::from("back 8"), Movement::Back(8));
+        assert_eq!(Movement::from("left 8"), Movement::Left(8));
+        assert_eq!(Movement::from("right 8"), Movement::Right(8));
+    }
+
+    #[test]
+    fn test_movement_parsing_errors() {
+        assert_eq!("forward".parse::<Movement>(), Err(ParseMovementError));
+        assert_eq!("forward -1".parse::<Movement>(), Err(ParseMovementError));
+        assert_eq!("sideways 1".parse::<Movement>(), Err(ParseMovementError));
+        assert_eq!("down 1 2".parse::<Movement>(), Err(ParseMovementError));
+    }
+
+    #[test]
+    fn test_movement_display() {
+        for line in ["forward 8", "down 0", "up 8", "back 8", "left 8", "right 8"] {
+            assert_eq!(Movement::from(line).to_string(), line);
+        }
+    }
+}
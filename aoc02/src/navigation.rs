@@ -0,0 +1,69 @@
+use aoc::arith::{add, mul, sub};
+
+use crate::{Movement, OverflowError, Position};
+
+/// How a `Submarine` interprets its movements.
+pub trait NavigationModel {
+    fn steer(
+        &self,
+        pos: &mut Position,
+        aim: &mut i64,
+        movement: &Movement,
+    ) -> Result<(), OverflowError>;
+}
+
+/// Part 1: `down` and `up` change depth directly, and aim is unused.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Direct;
+
+impl NavigationModel for Direct {
+    fn steer(
+        &self,
+        pos: &mut Position,
+        _aim: &mut i64,
+        movement: &Movement,
+    ) -> Result<(), OverflowError> {
+        match *movement {
+            Movement::Down(n) => pos.vertical = add(pos.vertical, n.into())?,
+            Movement::Up(n) => pos.vertical = sub(pos.vertical, n.into())?,
+            Movement::Forward(n) => pos.horizontal = add(pos.horizontal, n.into())?,
+            Movement::Back(n) => pos.horizontal = sub(pos.horizontal, n.into())?,
+            Movement::Left(n) => pos.strafe(-i64::from(n))?,
+            Movement::Right(n) => pos.strafe(n.into())?,
+        };
+
+        Ok(())
+    }
+}
+
+/// Part 2: `down` and `up` change aim, and `forward` descends by aim times
+/// the distance moved. `back` retraces that slope in reverse, and `left` and
+/// `right` move sideways without changing depth.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Aimed;
+
+impl NavigationModel for Aimed {
+    fn steer(
+        &self,
+        pos: &mut Position,
+        aim: &mut i64,
+        movement: &Movement,
+    ) -> Result<(), OverflowError> {
+        match *movement {
+            Movement::Down(n) => *aim = add(*aim, n.into())?,
+            Movement::Up(n) => *aim = sub(*aim, n.into())?,
+            Movement::Forward(n) => {
+                pos.horizontal = add(pos.horizontal, n.into())?;
+                pos.vertical = add(pos.vertical, mul(*aim, n.into())?)?;
+            }
+            Movement::Back(n) => {
+                pos.horizontal = sub(pos.horizontal, n.into())?;
+                pos.vertical = sub(pos.vertical, mul(*aim, n.into())?)?;
+            }
+            Movement::Left(n) => pos.strafe(-i64::from(n))?,
+            Movement::Right(n) => pos.strafe(n.into())?,
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{load_script, Limits, Movement, NavigationError, Step, Submarine};
+
+const HELP: &str = "\
+Commands:
+  forward|down|up|back|left|right <n>  move the submarine
+  undo                                 take back the last movement
+  reset                                return to the start
+  load <file>                          run every movement in a script
+  help                                 show this message
+  quit                                 leave";
+
+/// An interactive session driving an aim-based submarine.
+#[derive(Default)]
+pub struct Repl {
+    sub: Submarine,
+    limits: Limits,
+    /// Every movement made, with the submarine's state before it.
+    history: Vec<(Movement, Step)>,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Repl::default()
+    }
+
+    /// A session whose submarine refuses any movement that would break
+    /// `limits`.
+    pub fn with_limits(limits: Limits) -> Self {
+        let mut repl = Repl::new();
+        repl.sub.set_limits(limits.clone());
+        repl.limits = limits;
+
+        repl
+    }
+
+    /// Handles one line of input and returns the reply to show, or `None`
+    /// once the user asks to quit.
+    pub fn eval(&mut self, line: &str) -> Option<String> {
+        let line = line.trim();
+
+        let reply = match line.split_once(' ').unwrap_or((line, "")) {
+            ("", _) => return Some(String::new()),
+            ("quit" | "exit", "") => return None,
+            ("help", "") => HELP.to_string(),
+            ("undo", "") => match self.history.pop() {
+                Some((movement, before)) => {
+                    self.sub.undo(before);
+                    format!("Undid {}\n{}", movement, self.status())
+                }
+                None => "Nothing to undo".to_string(),
+            },
+            ("reset", "") => {
+                self.history.clear();
+                self.sub = Submarine::new();
+                self.sub.set_limits(self.limits.clone());
+                self.status()
+            }
+            ("load", path) if !path.is_empty() => self.load(path.trim()),
+            _ => match line.parse::<Movement>() {
+                Ok(movement) => match self.traverse(movement) {
+                    Ok(()) => self.status(),
+                    Err(e) => format!("Can't move: {}", e),
+                },
+                Err(e) => format!("Unknown command `{}`: {}", line, e),
+            },
+        };
+
+        Some(reply)
+    }
+
+    /// Runs every movement in the script at `path`, stopping at the first
+    /// one the submarine can't make.
+    fn load(&mut self, path: &str) -> String {
+        let movements = match load_script(path) {
+            Ok(movements) => movements,
+            Err(e) => return format!("Failed to load {}: {}", path, e),
+        };
+
+        for &movement in &movements {
+            if let Err(e) = self.traverse(movement) {
+                return format!("Stopped loading {}: {}\n{}", path, e, self.status());
+            }
+        }
+
+        format!("Loaded {} movements\n{}", movements.len(), self.status())
+    }
+
+    fn traverse(&mut self, movement: Movement) -> Result<(), NavigationError> {
+        let before = Step {
+            pos: self.sub.pos,
+            aim: self.sub.aim,
+        };
+
+        self.sub.traverse(&movement)?;
+        self.history.push((movement, before));
+
+        Ok(())
+    }
+
+    fn status(&self) -> String {
+        format!("{}, aim {}", self.sub.pos, self.sub.aim)
+    }
+}
+
+/// Reads commands from `input` until it ends or the user quits, writing a
+/// prompt before each one and the reply after it. The submarine keeps to
+/// `limits`.
+pub fn run(input: impl BufRead, mut output: impl Write, limits: Limits) -> io::Result<()> {
+    let mut repl = Repl::with_limits(limits);
+
+    write!(output, "> ")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        match repl.eval(&line?) {
+            Some(reply) if reply.is_empty() => {}
+            Some(reply) => writeln!(output, "{}", reply)?,
+            None => return Ok(()),
+        }
+
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+
+    writeln!(output)
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_movements() {
+        let mut repl = Repl::new();
+
+        assert_eq!(
+            repl.eval("forward 5").as_deref(),
+            Some("horizontal 5, depth 0, aim 0")
+        );
+        assert_eq!(
+            repl.eval("  down 5 ").as_deref(),
+            Some("horizontal 5, depth 0, aim 5")
+        );
+        assert_eq!(
+            repl.eval("forward 8").as_deref(),
+            Some("horizontal 13, depth 40, aim 5")
+        );
+        assert_eq!(
+            repl.eval("right 2").as_deref(),
+            Some("horizontal 13, depth 40, lateral 2, aim 5")
+        );
+    }
+
+    #[test]
+    fn test_undo_and_reset() {
+        let mut repl = Repl::new();
+
+        assert_eq!(repl.eval("undo").as_deref(), Some("Nothing to undo"));
+
+        repl.eval("down 2");
+        repl.eval("forward 3");
+        assert_eq!(
+            repl.eval("undo").as_deref(),
+            Some("Undid forward 3\nhorizontal 0, depth 0, aim 2")
+        );
+
+        repl.eval("forward 1");
+        assert_eq!(
+            repl.eval("reset").as_deref(),
+            Some("horizontal 0, depth 0, aim 0")
+        );
+        assert_eq!(repl.eval("undo").as_deref(), Some("Nothing to undo"));
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut repl = Repl::with_limits(Limits {
+            max_depth: Some(10),
+            ..Limits::default()
+        });
+
+        repl.eval("down 2");
+        repl.eval("forward 5");
+        assert!(repl.eval("forward 1").unwrap().starts_with("Can't move:"));
+        assert_eq!(repl.history.len(), 2);
+
+        // Undoing puts the submarine back under the limit, and resetting
+        // keeps the limits.
+        repl.eval("undo");
+        assert_eq!(
+            repl.eval("forward 5").as_deref(),
+            Some("horizontal 5, depth 10, aim 2")
+        );
+        repl.eval("reset");
+        repl.eval("down 3");
+        assert!(repl.eval("forward 4").unwrap().starts_with("Can't move:"));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let mut repl = Repl::new();
+
+        assert!(repl
+            .eval("sideways 3")
+            .unwrap()
+            .starts_with("Unknown command `sideways 3`"));
+        assert_eq!(repl.eval("").as_deref(), Some(""));
+
+        repl.sub.pos.horizontal = i64::MAX;
+        assert_eq!(
+            repl.eval("forward 1").as_deref(),
+            Some("Can't move: step 0: arithmetic overflow")
+        );
+        assert!(repl.history.is_empty());
+    }
+
+    #[test]
+    fn test_load() {
+        let path = env::temp_dir().join(format!("aoc02-repl-{}.sub", std::process::id()));
+        fs::write(&path, "repeat 2 { down 1; forward 2 }\n").unwrap();
+
+        let mut repl = Repl::new();
+        let reply = repl.eval(&format!("load {}", path.display())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(reply, "Loaded 4 movements\nhorizontal 4, depth 6, aim 2");
+        assert_eq!(
+            repl.eval("undo").as_deref(),
+            Some("Undid forward 2\nhorizontal 2, depth 2, aim 2")
+        );
+
+        assert!(repl
+            .eval("load /nonexistent/dive.sub")
+            .unwrap()
+            .starts_with("Failed to load /nonexistent/dive.sub"));
+    }
+
+    #[test]
+    fn test_run() {
+        let mut output = Vec::new();
+        run(
+            "down 1\n\nforward 2\nquit\nforward 100\n".as_bytes(),
+            &mut output,
+            Limits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\
+> horizontal 0, depth 0, aim 1
+> > horizontal 2, depth 2, aim 1
+> "
+        );
+    }
+}
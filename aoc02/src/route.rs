@@ -0,0 +1,163 @@
+use crate::{Movement, Position};
+
+/// Plans the movements that take an aim-based (`Aimed`) submarine from
+/// `Position::start()` to `target`.
+///
+/// Uses as few movements as possible when every amount fits in a `u32`:
+/// none for the start, one to move straight ahead or back, two when the
+/// horizontal distance divides the depth (set the aim, then move), and three
+/// otherwise (move all but one step, set the aim to the depth, take the last
+/// step). Larger amounts are split into `u32::MAX`-sized movements, and a
+/// sideways offset adds a `left` or `right` at the end.
+pub fn route_to(target: &Position) -> Vec<Movement> {
+    let horizontal = i128::from(target.horizontal);
+    let vertical = i128::from(target.vertical);
+    let mut moves = Vec::new();
+
+    if vertical == 0 {
+        push_amount(&mut moves, horizontal, Movement::Forward, Movement::Back);
+    } else if horizontal != 0 && vertical % horizontal == 0 {
+        push_amount(
+            &mut moves,
+            vertical / horizontal,
+            Movement::Down,
+            Movement::Up,
+        );
+        push_amount(&mut moves, horizontal, Movement::Forward, Movement::Back);
+    } else {
+        push_amount(
+            &mut moves,
+            horizontal - 1,
+            Movement::Forward,
+            Movement::Back,
+        );
+        push_amount(&mut moves, vertical, Movement::Down, Movement::Up);
+        moves.push(Movement::Forward(1));
+    }
+
+    match target.lateral {
+        // Any sideways movement, even of zero, switches the lateral axis on.
+        Some(0) => moves.push(Movement::Right(0)),
+        Some(lateral) => push_amount(&mut moves, lateral.into(), Movement::Right, Movement::Left),
+        None => {}
+    }
+
+    moves
+}
+
+/// Pushes movements totalling `amount`, using `negative` if it's below zero.
+fn push_amount(
+    moves: &mut Vec<Movement>,
+    amount: i128,
+    positive: fn(u32) -> Movement,
+    negative: fn(u32) -> Movement,
+) {
+    let movement = if amount < 0 { negative } else { positive };
+    let mut remaining = amount.unsigned_abs();
+
+    while remaining > 0 {
+        let step = remaining.min(u32::MAX.into()) as u32;
+        moves.push(movement(step));
+        remaining -= u128::from(step);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{Aimed, Submarine};
+
+    fn replay(moves: &[Movement]) -> Position {
+        let mut sub = Submarine::<Aimed>::new();
+        sub.traverse_multiple(moves).unwrap();
+
+        sub.pos
+    }
+
+    fn position(horizontal: i64, vertical: i64) -> Position {
+        Position {
+            horizontal,
+            vertical,
+            lateral: None,
+        }
+    }
+
+    #[test]
+    fn test_route_to() {
+        assert_eq!(route_to(&Position::start()), vec![]);
+        assert_eq!(route_to(&position(5, 0)), vec![Movement::Forward(5)]);
+        assert_eq!(route_to(&position(-5, 0)), vec![Movement::Back(5)]);
+        assert_eq!(
+            route_to(&position(15, 60)),
+            vec![Movement::Down(4), Movement::Forward(15)]
+        );
+        assert_eq!(
+            route_to(&position(-3, 6)),
+            vec![Movement::Up(2), Movement::Back(3)]
+        );
+        assert_eq!(
+            route_to(&position(4, 6)),
+            vec![
+                Movement::Forward(3),
+                Movement::Down(6),
+                Movement::Forward(1)
+            ]
+        );
+        assert_eq!(
+            route_to(&position(0, -7)),
+            vec![Movement::Back(1), Movement::Up(7), Movement::Forward(1)]
+        );
+    }
+
+    #[test]
+    fn test_route_to_lateral() {
+        let target = Position {
+            horizontal: 2,
+            vertical: 0,
+            lateral: Some(-4),
+        };
+        assert_eq!(
+            route_to(&target),
+            vec![Movement::Forward(2), Movement::Left(4)]
+        );
+
+        let target = Position {
+            lateral: Some(0),
+            ..Position::start()
+        };
+        assert_eq!(replay(&route_to(&target)), target);
+    }
+
+    #[test]
+    fn test_route_to_large_amounts() {
+        let target = position(2 * i64::from(u32::MAX) + 1, 0);
+        let moves = route_to(&target);
+
+        assert_eq!(
+            moves,
+            vec![
+                Movement::Forward(u32::MAX),
+                Movement::Forward(u32::MAX),
+                Movement::Forward(1)
+            ]
+        );
+        assert_eq!(replay(&moves), target);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_route_reaches_target(
+            horizontal in -100_000i64..100_000,
+            vertical in -100_000i64..100_000,
+            lateral in proptest::option::of(-100_000i64..100_000),
+        ) {
+            let target = Position { horizontal, vertical, lateral };
+            let moves = route_to(&target);
+
+            prop_assert_eq!(replay(&moves), target);
+            prop_assert!(moves.len() <= 3 + usize::from(lateral.is_some()));
+        }
+    }
+}
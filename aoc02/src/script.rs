@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        alpha1, alphanumeric1, char, line_ending, multispace1, not_line_ending, space0, space1, u32,
+    },
+    combinator::{all_consuming, eof, map, peek, recognize, value, verify},
+    multi::many0,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use crate::Movement;
+
+/// A submarine script: movements, `repeat N { ... }` blocks and named
+/// `macro NAME { ... }` definitions. Statements are separated by newlines or
+/// `;`, and `#` starts a comment that runs to the end of the line.
+///
+/// ```text
+/// macro dive { down 5; forward 2 }
+///
+/// repeat 3 { forward 2; dive }  # descend in steps
+/// up 15
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Script {
+    statements: Vec<Statement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    Move(Movement),
+    Repeat(u32, Vec<Statement>),
+    Macro(String, Vec<Statement>),
+    Call(String),
+}
+
+impl Script {
+    /// The most movements a script may expand to, as nested `repeat`s
+    /// multiply quickly.
+    pub const MAX_MOVEMENTS: usize = 1_000_000;
+
+    /// Flattens the script into the movements it describes. Macros must be
+    /// defined before they are called, and can't call themselves, directly
+    /// or through other macros.
+    pub fn expand(&self) -> Result<Vec<Movement>, ScriptError> {
+        let mut expander = Expander {
+            macros: HashMap::new(),
+            calls: Vec::new(),
+            movements: Vec::new(),
+        };
+        expander.expand(&self.statements)?;
+
+        Ok(expander.movements)
+    }
+}
+
+impl FromStr for Script {
+    type Err = ScriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match all_consuming(statements)(s) {
+            Ok((_, statements)) => Ok(Script { statements }),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                let offset = s.len() - e.input.len();
+                let line = s[..offset].matches('\n').count() + 1;
+
+                Err(ScriptError::Syntax { line })
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The statement starting on `line` (1-based) couldn't be parsed.
+    Syntax { line: usize },
+    /// A macro was called before being defined.
+    UndefinedMacro(String),
+    /// A macro was called while it was already being expanded.
+    RecursiveMacro(String),
+    /// The script expands to more than `Script::MAX_MOVEMENTS` movements.
+    TooManyMovements,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Syntax { line } => write!(f, "syntax error on line {}", line),
+            ScriptError::UndefinedMacro(name) => write!(f, "undefined macro `{}`", name),
+            ScriptError::RecursiveMacro(name) => write!(f, "macro `{}` calls itself", name),
+            ScriptError::TooManyMovements => write!(
+                f,
+                "script expands to more than {} movements",
+                Script::MAX_MOVEMENTS
+            ),
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+/// Reads the script at `path` and expands it into the movements it
+/// describes.
+pub fn load_script(path: impl AsRef<Path>) -> Result<Vec<Movement>, Box<dyn Error>> {
+    let script = fs::read_to_string(path)?.parse::<Script>()?;
+
+    Ok(script.expand()?)
+}
+
+struct Expander<'a> {
+    macros: HashMap<&'a str, &'a [Statement]>,
+    /// The macros being expanded, innermost last.
+    calls: Vec<&'a str>,
+    movements: Vec<Movement>,
+}
+
+impl<'a> Expander<'a> {
+    fn expand(&mut self, statements: &'a [Statement]) -> Result<(), ScriptError> {
+        for statement in statements {
+            match statement {
+                Statement::Move(m) => {
+                    if self.movements.len() == Script::MAX_MOVEMENTS {
+                        return Err(ScriptError::TooManyMovements);
+                    }
+                    self.movements.push(*m);
+                }
+                Statement::Repeat(n, body) => {
+                    for _ in 0..*n {
+                        let before = self.movements.len();
+                        self.expand(body)?;
+
+                        // A body without movements adds nothing however
+                        // often it runs, so don't spin through the rest.
+                        if self.movements.len() == before {
+                            break;
+                        }
+                    }
+                }
+                Statement::Macro(name, body) => {
+                    self.macros.insert(name, body);
+                }
+                Statement::Call(name) => {
+                    let body = *self
+                        .macros
+                        .get(name.as_str())
+                        .ok_or_else(|| ScriptError::UndefinedMacro(name.clone()))?;
+
+                    if self.calls.contains(&name.as_str()) {
+                        return Err(ScriptError::RecursiveMacro(name.clone()));
+                    }
+
+                    self.calls.push(name);
+                    self.expand(body)?;
+                    self.calls.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const KEYWORDS: [&str; 8] = [
+    "forward", "down", "up", "back", "left", "right", "repeat", "macro",
+];
+
+fn statements(s: &str) -> IResult<&str, Vec<Statement>> {
+    preceded(
+        gap,
+        many0(terminated(statement, pair(end_of_statement, gap))),
+    )(s)
+}
+
+fn statement(s: &str) -> IResult<&str, Statement> {
+    alt((repeat, macro_definition, movement, call))(s)
+}
+
+fn movement(s: &str) -> IResult<&str, Statement> {
+    map(
+        separated_pair(
+            alt((
+                tag("forward"),
+                tag("down"),
+                tag("up"),
+                tag("back"),
+                tag("left"),
+                tag("right"),
+            )),
+            space1,
+            u32,
+        ),
+        |(direction, n)| {
+            Statement::Move(match direction {
+                "forward" => Movement::Forward(n),
+                "down" => Movement::Down(n),
+                "up" => Movement::Up(n),
+                "back" => Movement::Back(n),
+                "left" => Movement::Left(n),
+                "right" => Movement::Right(n),
+                _ => unreachable!(),
+            })
+        },
+    )(s)
+}
+
+fn repeat(s: &str) -> IResult<&str, Statement> {
+    map(
+        tuple((tag("repeat"), space1, u32, space0, block)),
+        |(_, _, n, _, body)| Statement::Repeat(n, body),
+    )(s)
+}
+
+fn macro_definition(s: &str) -> IResult<&str, Statement> {
+    map(
+        tuple((tag("macro"), space1, identifier, space0, block)),
+        |(_, _, name, _, body)| Statement::Macro(name.to_string(), body),
+    )(s)
+}
+
+fn call(s: &str) -> IResult<&str, Statement> {
+    map(identifier, |name| Statement::Call(name.to_string()))(s)
+}
+
+fn block(s: &str) -> IResult<&str, Vec<Statement>> {
+    delimited(char('{'), statements, char('}'))(s)
+}
+
+fn identifier(s: &str) -> IResult<&str, &str> {
+    verify(
+        recognize(pair(
+            alt((alpha1, tag("_"))),
+            many0(alt((alphanumeric1, tag("_")))),
+        )),
+        |name: &str| !KEYWORDS.contains(&name),
+    )(s)
+}
+
+/// Whatever ends a statement: a `;`, the end of the line (possibly after a
+/// comment), the end of the enclosing block, or the end of the script.
+fn end_of_statement(s: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        preceded(
+            space0,
+            alt((tag(";"), line_ending, comment, peek(tag("}")), eof)),
+        ),
+    )(s)
+}
+
+/// Any run of whitespace, blank lines, stray `;`s and comments.
+fn gap(s: &str) -> IResult<&str, ()> {
+    value((), many0(alt((multispace1, tag(";"), comment))))(s)
+}
+
+fn comment(s: &str) -> IResult<&str, &str> {
+    recognize(pair(char('#'), not_line_ending))(s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Aimed, Position, Submarine};
+
+    #[test]
+    fn test_plain_movements() {
+        let script: Script = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n"
+            .parse()
+            .unwrap();
+
+        let mut sub = Submarine::<Aimed>::new();
+        sub.traverse_multiple(&script.expand().unwrap()).unwrap();
+        assert_eq!(
+            sub.pos,
+            Position {
+                horizontal: 15,
+                vertical: 60,
+                lateral: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let script: Script = "
+            # Get going
+            forward 5   # full speed
+
+            down 2; forward 1;
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            script.expand(),
+            Ok(vec![
+                Movement::Forward(5),
+                Movement::Down(2),
+                Movement::Forward(1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_repeat() {
+        let script: Script = "repeat 3 { forward 2; down 1 }".parse().unwrap();
+
+        assert_eq!(
+            script.expand().unwrap(),
+            [Movement::Forward(2), Movement::Down(1)].repeat(3)
+        );
+
+        let script: Script = "repeat 2 {\n  left 1\n  repeat 2 { up 1 }\n}\nrepeat 0 { back 9 }"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            script.expand(),
+            Ok(vec![
+                Movement::Left(1),
+                Movement::Up(1),
+                Movement::Up(1),
+                Movement::Left(1),
+                Movement::Up(1),
+                Movement::Up(1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_macros() {
+        let script: Script = "
+            macro dive { down 5; forward 2 }
+            macro deep_dive { dive; dive }
+            deep_dive
+            right 1
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            script.expand(),
+            Ok(vec![
+                Movement::Down(5),
+                Movement::Forward(2),
+                Movement::Down(5),
+                Movement::Forward(2),
+                Movement::Right(1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_undefined_macro() {
+        let script: Script = "surface\nmacro surface { up 10 }".parse().unwrap();
+
+        assert_eq!(
+            script.expand(),
+            Err(ScriptError::UndefinedMacro("surface".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_recursive_macros() {
+        let script: Script = "macro a { a }\na".parse().unwrap();
+        assert_eq!(
+            script.expand(),
+            Err(ScriptError::RecursiveMacro("a".to_string()))
+        );
+
+        let script: Script = "macro a { forward 1; b }\nmacro b { down 1; a }\na"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            script.expand(),
+            Err(ScriptError::RecursiveMacro("a".to_string()))
+        );
+
+        // Calling a macro twice in a row isn't recursion.
+        let script: Script = "macro a { up 1 }\nmacro b { a; a }\nb".parse().unwrap();
+        assert_eq!(script.expand(), Ok(vec![Movement::Up(1); 2]));
+    }
+
+    #[test]
+    fn test_too_many_movements() {
+        let script: Script = "repeat 1000 { repeat 1000 { forward 1 } }".parse().unwrap();
+        assert_eq!(script.expand().unwrap().len(), Script::MAX_MOVEMENTS);
+
+        let script: Script = "repeat 1000 { repeat 1000 { forward 1 } }\nup 1"
+            .parse()
+            .unwrap();
+        assert_eq!(script.expand(), Err(ScriptError::TooManyMovements));
+
+        // Bodies without movements end early, however often they repeat.
+        let script: Script =
+            "macro nothing { }\nrepeat 4000000000 { repeat 4000000000 { nothing } }"
+                .parse()
+                .unwrap();
+        assert_eq!(script.expand(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(
+            "forward 1\nforward -2\n".parse::<Script>(),
+            Err(ScriptError::Syntax { line: 2 })
+        );
+        assert_eq!(
+            "forward 1 down 2".parse::<Script>(),
+            Err(ScriptError::Syntax { line: 1 })
+        );
+        assert_eq!(
+            "\n\nrepeat 2 { forward 1".parse::<Script>(),
+            Err(ScriptError::Syntax { line: 3 })
+        );
+        assert_eq!(
+            "macro up { down 1 }".parse::<Script>(),
+            Err(ScriptError::Syntax { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_load_script() {
+        let path = std::env::temp_dir().join(format!("aoc02-script-{}.txt", std::process::id()));
+        let load = |contents: &str| {
+            std::fs::write(&path, contents).unwrap();
+            load_script(&path).map_err(|e| e.to_string())
+        };
+
+        assert_eq!(
+            load("repeat 2 { forward 1 }"),
+            Ok(vec![Movement::Forward(1), Movement::Forward(1)])
+        );
+        assert_eq!(
+            load("forward -1"),
+            Err("syntax error on line 1".to_string())
+        );
+        assert_eq!(load("dive"), Err("undefined macro `dive`".to_string()));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(load_script(&path).is_err());
+    }
+}
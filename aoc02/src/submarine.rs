@@ -0,0 +1,325 @@
+use std::fmt;
+
+use aoc::arith::{add, mul};
+
+use crate::{
+    Aimed, Limits, Movement, NavigationError, NavigationModel, OverflowError, Step, Trajectory,
+    Violation,
+};
+
+pub struct Submarine<N = Aimed> {
+    pub pos: Position,
+    pub aim: i64,
+    model: N,
+    limits: Limits,
+    steps: usize,
+    trajectory: Option<Trajectory>,
+}
+
+impl<N: NavigationModel + Default> Submarine<N> {
+    pub fn new() -> Self {
+        Submarine::with_model(N::default())
+    }
+}
+
+impl<N: NavigationModel + Default> Default for Submarine<N> {
+    fn default() -> Self {
+        Submarine::new()
+    }
+}
+
+impl<N: NavigationModel> Submarine<N> {
+    pub fn with_model(model: N) -> Self {
+        Submarine {
+            pos: Position::start(),
+            aim: 0,
+            model,
+            limits: Limits::default(),
+            steps: 0,
+            trajectory: None,
+        }
+    }
+
+    /// Refuses any later movement that would break `limits`.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// How many movements the submarine has made.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Starts recording the state after every movement from here on,
+    /// discarding anything recorded before.
+    pub fn start_recording(&mut self) {
+        self.trajectory = Some(Trajectory::new(self.step()));
+    }
+
+    /// Stops recording and hands back what was recorded, if anything.
+    pub fn stop_recording(&mut self) -> Option<Trajectory> {
+        self.trajectory.take()
+    }
+
+    pub fn trajectory(&self) -> Option<&Trajectory> {
+        self.trajectory.as_ref()
+    }
+
+    pub fn traverse_multiple(&mut self, moves: &[Movement]) -> Result<(), NavigationError> {
+        for m in moves {
+            self.traverse(m)?;
+        }
+
+        Ok(())
+    }
+
+    /// Goes back to where `trajectory` started and makes its movements again,
+    /// under this submarine's own navigation model and limits. Stops at the
+    /// first movement that fails, like `traverse_multiple`.
+    ///
+    /// Steps are counted from the start of the replay, and an active
+    /// recording starts over from there too.
+    pub fn replay(&mut self, trajectory: &Trajectory) -> Result<(), NavigationError> {
+        let start = trajectory.start();
+        self.pos = start.pos;
+        self.aim = start.aim;
+        self.steps = 0;
+
+        if self.trajectory.is_some() {
+            self.start_recording();
+        }
+
+        self.traverse_multiple(trajectory.movements())
+    }
+
+    /// Makes `movement`, unless that would overflow or break the
+    /// submarine's limits.
+    pub fn traverse(&mut self, movement: &Movement) -> Result<(), NavigationError> {
+        let (mut pos, mut aim) = (self.pos, self.aim);
+
+        self.model
+            .steer(&mut pos, &mut aim, movement)
+            .map_err(Violation::from)
+            .and_then(|()| self.limits.check(&self.pos, &pos, aim))
+            .map_err(|violation| NavigationError {
+                step: self.steps,
+                violation,
+            })?;
+
+        self.pos = pos;
+        self.aim = aim;
+        self.steps += 1;
+
+        let step = self.step();
+        if let Some(trajectory) = self.trajectory.as_mut() {
+            trajectory.push(*movement, step);
+        }
+
+        Ok(())
+    }
+
+    /// Takes back the last movement, which started from `before`. Anything
+    /// recorded stays recorded.
+    pub(crate) fn undo(&mut self, before: Step) {
+        self.pos = before.pos;
+        self.aim = before.aim;
+        self.steps -= 1;
+    }
+
+    fn step(&self) -> Step {
+        Step {
+            pos: self.pos,
+            aim: self.aim,
+        }
+    }
+}
+
+/// Where the submarine is. Depth (`vertical`) grows downwards and goes
+/// negative above the surface.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: i64,
+    pub vertical: i64,
+    /// Sideways offset, positive to the right. `None` until the submarine
+    /// first moves `left` or `right`.
+    pub lateral: Option<i64>,
+}
+
+impl Position {
+    pub fn start() -> Self {
+        Position {
+            horizontal: 0,
+            vertical: 0,
+            lateral: None,
+        }
+    }
+
+    /// The puzzle answer: horizontal position multiplied by depth.
+    pub fn product(&self) -> Result<i64, OverflowError> {
+        mul(self.horizontal, self.vertical)
+    }
+
+    /// The straight-line distance between two positions, treating a missing
+    /// lateral axis as zero.
+    pub fn distance_to(&self, other: &Position) -> f64 {
+        let delta = |a: i64, b: i64| (b as f64 - a as f64).powi(2);
+
+        (delta(self.horizontal, other.horizontal)
+            + delta(self.vertical, other.vertical)
+            + delta(self.lateral.unwrap_or(0), other.lateral.unwrap_or(0)))
+        .sqrt()
+    }
+
+    pub(crate) fn strafe(&mut self, by: i64) -> Result<(), OverflowError> {
+        self.lateral = Some(add(self.lateral.unwrap_or(0), by)?);
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "horizontal {}, depth {}", self.horizontal, self.vertical)?;
+
+        if let Some(lateral) = self.lateral {
+            write!(f, ", lateral {}", lateral)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Direct;
+
+    const EXAMPLE: [Movement; 6] = [
+        Movement::Forward(5),
+        Movement::Down(5),
+        Movement::Forward(8),
+        Movement::Up(3),
+        Movement::Down(8),
+        Movement::Forward(2),
+    ];
+
+    #[test]
+    fn test_traverse() {
+        let mut sub = Submarine::<Aimed>::new();
+
+        sub.traverse_multiple(&[]).unwrap();
+        assert_eq!(sub.pos, Position::start());
+
+        sub.traverse_multiple(&EXAMPLE).unwrap();
+        assert_eq!(
+            sub.pos,
+            Position {
+                horizontal: 15,
+                vertical: 60,
+                lateral: None,
+            }
+        );
+        assert_eq!(sub.aim, 10);
+    }
+
+    #[test]
+    fn test_traverse_direct() {
+        let mut sub = Submarine::<Direct>::new();
+
+        sub.traverse_multiple(&EXAMPLE).unwrap();
+        assert_eq!(
+            sub.pos,
+            Position {
+                horizontal: 15,
+                vertical: 10,
+                lateral: None,
+            }
+        );
+        assert_eq!(sub.aim, 0);
+        assert_eq!(sub.pos.product(), Ok(150));
+    }
+
+    #[test]
+    fn test_traverse_3d() {
+        let moves = [
+            Movement::Down(2),
+            Movement::Forward(4),
+            Movement::Right(3),
+            Movement::Back(1),
+            Movement::Left(5),
+        ];
+
+        let mut sub = Submarine::<Aimed>::new();
+        sub.traverse_multiple(&moves).unwrap();
+        assert_eq!(
+            sub.pos,
+            Position {
+                horizontal: 3,
+                vertical: 6,
+                lateral: Some(-2),
+            }
+        );
+
+        let mut sub = Submarine::<Direct>::new();
+        sub.traverse_multiple(&moves).unwrap();
+        assert_eq!(
+            sub.pos,
+            Position {
+                horizontal: 3,
+                vertical: 2,
+                lateral: Some(-2),
+            }
+        );
+    }
+
+    #[test]
+    fn test_traverse_above_surface() {
+        let mut sub = Submarine::<Aimed>::new();
+        sub.traverse_multiple(&[Movement::Up(3), Movement::Forward(2)])
+            .unwrap();
+        assert_eq!(sub.pos.vertical, -6);
+        assert_eq!(sub.pos.product(), Ok(-12));
+
+        let mut sub = Submarine::<Direct>::new();
+        sub.traverse(&Movement::Up(1)).unwrap();
+        assert_eq!(sub.pos.vertical, -1);
+    }
+
+    #[test]
+    fn test_traverse_overflow() {
+        let mut sub = Submarine::<Aimed>::new();
+        assert_eq!(
+            sub.traverse_multiple(&[
+                Movement::Down(u32::MAX),
+                Movement::Down(u32::MAX),
+                Movement::Down(u32::MAX),
+                Movement::Forward(u32::MAX),
+            ]),
+            Err(NavigationError {
+                step: 3,
+                violation: Violation::Overflow
+            })
+        );
+
+        let mut sub = Submarine::<Direct>::new();
+        sub.pos.horizontal = i64::MAX;
+        assert_eq!(
+            sub.traverse(&Movement::Forward(1)),
+            Err(NavigationError {
+                step: 0,
+                violation: Violation::Overflow
+            })
+        );
+        assert_eq!(sub.pos.horizontal, i64::MAX);
+
+        let mut sub = Submarine::<Direct>::new();
+        sub.pos.lateral = Some(i64::MIN);
+        assert_eq!(
+            sub.traverse(&Movement::Left(1)),
+            Err(NavigationError {
+                step: 0,
+                violation: Violation::Overflow
+            })
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::Write;
+
+use crate::{Movement, Position};
+
+/// The submarine's state after a movement.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub pos: Position,
+    pub aim: i64,
+}
+
+/// Every state a submarine passed through while recording, and the
+/// movements that took it there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trajectory {
+    start: Step,
+    steps: Vec<Step>,
+    movements: Vec<Movement>,
+}
+
+impl Trajectory {
+    pub(crate) fn new(start: Step) -> Self {
+        Trajectory {
+            start,
+            steps: Vec::new(),
+            movements: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, movement: Movement, step: Step) {
+        self.movements.push(movement);
+        self.steps.push(step);
+    }
+
+    /// Where recording started, before the first movement.
+    pub fn start(&self) -> Step {
+        self.start
+    }
+
+    /// The state after each recorded movement, in order.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The recorded movements, in order, one per step.
+    pub fn movements(&self) -> &[Movement] {
+        &self.movements
+    }
+
+    /// The deepest point reached, including the starting position.
+    pub fn max_depth(&self) -> i64 {
+        self.states().map(|s| s.pos.vertical).max().unwrap()
+    }
+
+    /// The index of the first movement that left the submarine deeper than
+    /// `depth`.
+    pub fn first_deeper_than(&self, depth: i64) -> Option<usize> {
+        self.steps.iter().position(|s| s.pos.vertical > depth)
+    }
+
+    /// The straight-line distance covered by each movement, summed.
+    pub fn total_distance(&self) -> f64 {
+        self.states()
+            .zip(&self.steps)
+            .map(|(from, to)| from.pos.distance_to(&to.pos))
+            .sum()
+    }
+
+    /// One row per state, starting with step 0 for the starting position. The
+    /// lateral column is left empty until the submarine first moves sideways.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,horizontal,vertical,lateral,aim\n");
+
+        for (i, state) in self.states().enumerate() {
+            let lateral = state.pos.lateral.map(|l| l.to_string());
+
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                i,
+                state.pos.horizontal,
+                state.pos.vertical,
+                lateral.as_deref().unwrap_or(""),
+                state.aim
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    fn states(&self) -> impl Iterator<Item = &Step> {
+        std::iter::once(&self.start).chain(&self.steps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Aimed, Direct, Limits, Movement, Submarine};
+
+    fn example() -> Submarine {
+        let mut sub = Submarine::<Aimed>::new();
+        sub.start_recording();
+        sub.traverse_multiple(&[
+            Movement::Forward(5),
+            Movement::Down(5),
+            Movement::Forward(8),
+            Movement::Up(3),
+            Movement::Down(8),
+            Movement::Forward(2),
+        ])
+        .unwrap();
+
+        sub
+    }
+
+    #[test]
+    fn test_recording() {
+        let sub = example();
+        let trajectory = sub.trajectory().unwrap();
+
+        assert_eq!(trajectory.steps().len(), 6);
+        assert_eq!(trajectory.steps().last().unwrap().pos, sub.pos);
+        assert_eq!(trajectory.steps()[3].aim, 2);
+
+        assert!(Submarine::<Aimed>::new().trajectory().is_none());
+    }
+
+    #[test]
+    fn test_replay() {
+        let recorded = example();
+        let trajectory = recorded.trajectory().unwrap();
+
+        let mut sub = Submarine::<Aimed>::new();
+        sub.traverse(&Movement::Down(100)).unwrap();
+        sub.start_recording();
+        sub.replay(trajectory).unwrap();
+
+        assert_eq!(sub.pos, recorded.pos);
+        assert_eq!(sub.aim, recorded.aim);
+        assert_eq!(sub.trajectory().unwrap().steps(), trajectory.steps());
+
+        // Under the direct model the same movements end up elsewhere.
+        let mut direct = Submarine::<Direct>::new();
+        direct.replay(trajectory).unwrap();
+        assert_eq!(direct.pos.product(), Ok(150));
+
+        // A replay is refused at the same step as the original would be.
+        let mut limited = Submarine::<Aimed>::new();
+        limited.set_limits(Limits {
+            max_depth: Some(50),
+            ..Limits::default()
+        });
+        assert_eq!(limited.replay(trajectory).unwrap_err().step, 5);
+    }
+
+    #[test]
+    fn test_replay_while_recording() {
+        let recorded = example().stop_recording().unwrap();
+
+        let mut sub = Submarine::<Aimed>::new();
+        sub.traverse(&Movement::Down(100)).unwrap();
+        sub.start_recording();
+        sub.replay(&recorded).unwrap();
+        assert_eq!(sub.steps(), 6);
+
+        // The new recording matches the original, not the dive before it.
+        assert_eq!(sub.stop_recording(), Some(recorded));
+        assert_eq!(sub.trajectory(), None);
+
+        // Errors count steps from the start of the replay.
+        sub.set_limits(Limits {
+            max_depth: Some(50),
+            ..Limits::default()
+        });
+        sub.start_recording();
+        let err = sub
+            .replay(&example().stop_recording().unwrap())
+            .unwrap_err();
+        assert_eq!(err.step, 5);
+        assert_eq!(sub.trajectory().unwrap().steps().len(), 5);
+    }
+
+    #[test]
+    fn test_queries() {
+        let sub = example();
+        let trajectory = sub.trajectory().unwrap();
+
+        assert_eq!(trajectory.max_depth(), 60);
+        assert_eq!(trajectory.first_deeper_than(0), Some(2));
+        assert_eq!(trajectory.first_deeper_than(40), Some(5));
+        assert_eq!(trajectory.first_deeper_than(60), None);
+
+        // 5 along, then (8, 40), then (2, 20).
+        let expected =
+            5.0 + (8.0f64 * 8.0 + 40.0 * 40.0).sqrt() + (2.0f64 * 2.0 + 20.0 * 20.0).sqrt();
+        assert!((trajectory.total_distance() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut sub = Submarine::<Aimed>::new();
+        sub.start_recording();
+        sub.traverse_multiple(&[Movement::Down(2), Movement::Forward(3), Movement::Right(1)])
+            .unwrap();
+
+        assert_eq!(
+            sub.trajectory().unwrap().to_csv(),
+            "\
+step,horizontal,vertical,lateral,aim
+0,0,0,,0
+1,0,0,,2
+2,3,6,,2
+3,3,6,1,2
+"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Compares the packed report against the original `PeekNth<Chars>`
+//! implementation with:
+//!
+//! ```sh
+//! cargo bench --bench diagnostics
+//! ```
+//!
+//! The life support ratings come from a `BitTrie`, whose build is benched
+//! on its own alongside the walks that use it.
+//!
+//! The serial and parallel column counting with:
+//!
+//! ```sh
+//! cargo bench --bench diagnostics -- --save-baseline serial
+//! cargo bench --bench diagnostics --features parallel -- --baseline serial
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc03::{BitPolicy, BitTrie, DiagnosticReport, PackedReport};
+use num_bigint::BigUint;
+
+/// Generates `n` random binary strings of `width` bits using a fixed-seed
+/// LCG, so that every run benchmarks the same input.
+fn generate_input(n: usize, width: usize) -> Vec<String> {
+    let mut state: u64 = 0x2021_0003;
+
+    (0..n)
+        .map(|_| {
+            (0..width)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    if state >> 63 == 0 {
+                        '0'
+                    } else {
+                        '1'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_report(c: &mut Criterion) {
+    let input = generate_input(100_000, 48);
+    let mut lines = input.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let report = DiagnosticReport::new(&lines).unwrap();
+    let [gamma, epsilon, o2, co2] = legacy::rates(&mut lines).map(BigUint::from);
+    assert_eq!(report.power_consumption(), gamma * epsilon);
+    assert_eq!(report.life_support_rating(), o2 * co2);
+
+    c.bench_function("report 100k lines", |b| {
+        b.iter(|| DiagnosticReport::new(&lines).unwrap())
+    });
+
+    c.bench_function("peek_nth report 100k lines", |b| {
+        b.iter(|| legacy::rates(&mut lines))
+    });
+
+    let packed = PackedReport::parse(&lines).unwrap();
+    let trie = BitTrie::new(&packed);
+
+    c.bench_function("bit trie build 100k lines", |b| {
+        b.iter(|| BitTrie::new(&packed))
+    });
+
+    c.bench_function("bit trie ratings 100k lines", |b| {
+        b.iter(|| {
+            (
+                trie.rating(BitPolicy::MOST_COMMON).unwrap(),
+                trie.rating(BitPolicy::LEAST_COMMON).unwrap(),
+            )
+        })
+    });
+}
+
+/// The original implementation, which walked every line as a
+/// `PeekNth<Chars>`, kept here as a baseline for the packed representation.
+mod legacy {
+    use std::cmp::Ordering;
+    use std::str::Chars;
+
+    use itertools::{peek_nth, PeekNth};
+
+    /// Returns the gamma, epsilon, O2 generator and CO2 scrubber ratings.
+    pub fn rates(input: &mut [&str]) -> [usize; 4] {
+        let line_length = input.first().unwrap().len();
+
+        let mut lines = input
+            .iter_mut()
+            .map(|l| peek_nth(l.chars()))
+            .collect::<Vec<_>>();
+
+        let (gamma_rate, epsilon_rate) = get_gamma_epsilon_rates(&mut lines, line_length);
+        let o2_gen_rating = get_lsr_rating_component(&mut lines, 0, LsrBitCriteria::MostCommon);
+        let co2_scrubber_rating =
+            get_lsr_rating_component(&mut lines, 0, LsrBitCriteria::LeastCommon);
+
+        [gamma_rate, epsilon_rate, o2_gen_rating, co2_scrubber_rating]
+    }
+
+    fn get_gamma_epsilon_rates(input: &mut [PeekNth<Chars>], line_length: usize) -> (usize, usize) {
+        let mut gamma_rate_string = String::new();
+        let mut epsilon_rate_string = String::new();
+
+        for i in 0..line_length {
+            let bin_info = BinaryStringInfo::new_for_idx(input, i);
+
+            if bin_info.num_zeroes > bin_info.num_ones {
+                gamma_rate_string.push('0');
+                epsilon_rate_string.push('1');
+            } else {
+                gamma_rate_string.push('1');
+                epsilon_rate_string.push('0');
+            }
+        }
+
+        (
+            usize_from_binary(&gamma_rate_string),
+            usize_from_binary(&epsilon_rate_string),
+        )
+    }
+
+    enum LsrBitCriteria {
+        MostCommon,
+        LeastCommon,
+    }
+
+    fn get_lsr_rating_component(
+        input: &mut [PeekNth<Chars>],
+        cur_idx: usize,
+        bit_crit: LsrBitCriteria,
+    ) -> usize {
+        if input.len() == 1 {
+            return usize_from_binary(&input.get_mut(0).unwrap().collect::<String>());
+        }
+
+        let bin_info = BinaryStringInfo::new_for_idx(input, cur_idx);
+
+        // The one change from the original: it recursed forever once every
+        // remaining line shared a bit, which random input quickly hits. See
+        // `BitPolicy::keep_for_rating`.
+        if bin_info.num_zeroes == 0 || bin_info.num_ones == 0 {
+            return get_lsr_rating_component(input, cur_idx + 1, bit_crit);
+        }
+
+        let most_common_bit: u8 = match bin_info.num_zeroes.cmp(&bin_info.num_ones) {
+            Ordering::Less | Ordering::Equal => 1,
+            Ordering::Greater => 0,
+        };
+
+        let char_to_keep = (match bit_crit {
+            LsrBitCriteria::MostCommon => most_common_bit,
+            LsrBitCriteria::LeastCommon => most_common_bit ^ 0b0000_0001,
+        } + b'0') as char;
+
+        let mut filtered_input: Vec<PeekNth<Chars>> = input
+            .iter_mut()
+            .filter_map(|line| {
+                if *line.peek_nth(cur_idx).unwrap() == char_to_keep {
+                    Some(line.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        get_lsr_rating_component(&mut filtered_input, cur_idx + 1, bit_crit)
+    }
+
+    fn usize_from_binary(binary: &str) -> usize {
+        usize::from_str_radix(binary, 2).unwrap()
+    }
+
+    struct BinaryStringInfo {
+        num_zeroes: usize,
+        num_ones: usize,
+    }
+
+    impl BinaryStringInfo {
+        fn new_for_idx(input: &mut [PeekNth<Chars>], n: usize) -> Self {
+            let num_zeroes = input
+                .iter_mut()
+                .map(|l| *l.peek_nth(n).unwrap())
+                .filter(|&c| c == '0')
+                .count();
+            let num_ones = input.len() - num_zeroes;
+
+            BinaryStringInfo {
+                num_zeroes,
+                num_ones,
+            }
+        }
+    }
+}
+
+criterion_group!(benches, bench_report);
+criterion_main!(benches);
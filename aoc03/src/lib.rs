@@ -0,0 +1,468 @@
+use std::error::Error;
+use std::fmt;
+
+use num_bigint::BigUint;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+mod policy;
+mod stats;
+mod trie;
+
+pub use policy::{BitCriteria, BitPolicy, Policies, TieBreak};
+pub use stats::{Column, ColumnStats};
+pub use trie::BitTrie;
+
+#[derive(Debug)]
+pub struct DiagnosticReport {
+    gamma_rate: BigUint,
+    epsilon_rate: BigUint,
+    o2_gen_rating: BigUint,
+    co2_scrubber_rating: BigUint,
+}
+
+impl DiagnosticReport {
+    pub fn new(input: &[&str]) -> Result<Self, ReportError> {
+        DiagnosticReport::with_policies(input, &Policies::default())
+    }
+
+    /// Builds the report, picking each value's bits with its own policy.
+    pub fn with_policies(input: &[&str], policies: &Policies) -> Result<Self, ReportError> {
+        let packed = PackedReport::parse(input)?;
+
+        let columns = column_counts(&packed);
+        let trie = BitTrie::new(&packed);
+
+        Ok(DiagnosticReport {
+            gamma_rate: get_rate(&columns, policies.gamma)?,
+            epsilon_rate: get_rate(&columns, policies.epsilon)?,
+            o2_gen_rating: trie.rating(policies.o2_gen)?,
+            co2_scrubber_rating: trie.rating(policies.co2_scrubber)?,
+        })
+    }
+
+    pub fn power_consumption(&self) -> BigUint {
+        &self.gamma_rate * &self.epsilon_rate
+    }
+
+    pub fn life_support_rating(&self) -> BigUint {
+        &self.o2_gen_rating * &self.co2_scrubber_rating
+    }
+}
+
+/// The power consumption of an already parsed report, computing only what
+/// part 1 needs.
+pub fn part_1(report: &PackedReport) -> Result<BigUint, ReportError> {
+    let columns = column_counts(report);
+
+    Ok(get_rate(&columns, BitPolicy::MOST_COMMON)? * get_rate(&columns, BitPolicy::LEAST_COMMON)?)
+}
+
+/// The life support rating of an already parsed report, computing only what
+/// part 2 needs.
+pub fn part_2(report: &PackedReport) -> Result<BigUint, ReportError> {
+    let trie = BitTrie::new(report);
+
+    Ok(trie.rating(BitPolicy::MOST_COMMON)? * trie.rating(BitPolicy::LEAST_COMMON)?)
+}
+
+/// A report's lines parsed once into a bitset of any width, stored as
+/// `limbs` consecutive `u64`s per line with the least significant first.
+/// Column 0 is the leftmost character, which becomes the most significant
+/// of the `width` bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedReport {
+    bits: Vec<u64>,
+    width: usize,
+    limbs: usize,
+    len: usize,
+}
+
+impl PackedReport {
+    /// Checks that there's at least one line, that every line is as wide
+    /// as the first, and that they only contain `0` and `1`.
+    pub fn parse(input: &[&str]) -> Result<Self, ReportError> {
+        let width = input.first().ok_or(ReportError::Empty)?.len();
+        let limbs = width.div_ceil(64).max(1);
+        let mut bits = vec![0; input.len() * limbs];
+
+        for (i, (line, words)) in input.iter().zip(bits.chunks_exact_mut(limbs)).enumerate() {
+            if line.len() != width {
+                return Err(ReportError::InconsistentWidth {
+                    line: i + 1,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+
+            for (column, c) in line.bytes().enumerate() {
+                match c {
+                    b'0' => {}
+                    b'1' => {
+                        let (limb, mask) = bit_position(width, column);
+                        words[limb] |= mask;
+                    }
+                    _ => {
+                        return Err(ReportError::InvalidDigit {
+                            line: i + 1,
+                            column: column + 1,
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(PackedReport {
+            bits,
+            width,
+            limbs,
+            len: input.len(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether `column` of line `line` is a `1`.
+    pub(crate) fn bit(&self, line: usize, column: usize) -> bool {
+        let (limb, mask) = bit_position(self.width, column);
+        self.bits[line * self.limbs + limb] & mask != 0
+    }
+
+    /// The value of line `line`.
+    pub(crate) fn value(&self, line: usize) -> BigUint {
+        let words = &self.bits[line * self.limbs..(line + 1) * self.limbs];
+
+        BigUint::new(
+            words
+                .iter()
+                .flat_map(|&w| [w as u32, (w >> 32) as u32])
+                .collect(),
+        )
+    }
+}
+
+/// Which limb holds `column` in a line `width` bits wide, and the mask for
+/// it within that limb.
+fn bit_position(width: usize, column: usize) -> (usize, u64) {
+    let bit = width - 1 - column;
+    (bit / 64, 1 << (bit % 64))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportError {
+    Empty,
+    /// Line numbers are 1-based.
+    InconsistentWidth {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Line and column numbers are 1-based.
+    InvalidDigit {
+        line: usize,
+        column: usize,
+    },
+    /// A column (1-based) had as many zeroes as ones under `TieBreak::Error`.
+    Tie {
+        column: usize,
+    },
+    /// A rate's column (1-based) was tied under `TieBreak::KeepBoth`.
+    AmbiguousRate {
+        column: usize,
+    },
+    /// Keeping both bits on ties left a rating with `remaining` lines to
+    /// choose from, and they differ.
+    AmbiguousRating {
+        remaining: usize,
+    },
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::Empty => write!(f, "the report is empty"),
+            ReportError::InconsistentWidth {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {} bits wide, but the first line is {}",
+                line, found, expected
+            ),
+            ReportError::InvalidDigit { line, column } => {
+                write!(f, "line {} column {} isn't a 0 or 1", line, column)
+            }
+            ReportError::Tie { column } => {
+                write!(f, "column {} has as many zeroes as ones", column)
+            }
+            ReportError::AmbiguousRate { column } => write!(
+                f,
+                "column {} is tied, and a rate can't keep both bits",
+                column
+            ),
+            ReportError::AmbiguousRating { remaining } => write!(
+                f,
+                "{} different lines are left to choose the rating from",
+                remaining
+            ),
+        }
+    }
+}
+
+impl Error for ReportError {}
+
+fn column_counts(report: &PackedReport) -> Vec<BinaryStringInfo> {
+    (0..report.width())
+        .map(|i| BinaryStringInfo::new_for_idx(report, i))
+        .collect()
+}
+
+/// Builds a rate from each column's counts, one bit per column.
+fn get_rate(columns: &[BinaryStringInfo], policy: BitPolicy) -> Result<BigUint, ReportError> {
+    let mut rate = BigUint::default();
+
+    for (i, bin_info) in columns.iter().enumerate() {
+        match policy.keep(i, bin_info.num_zeroes, bin_info.num_ones)? {
+            [true, true] => return Err(ReportError::AmbiguousRate { column: i + 1 }),
+            [_, one] => rate.set_bit((columns.len() - 1 - i) as u64, one),
+        }
+    }
+
+    Ok(rate)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct BinaryStringInfo {
+    num_zeroes: usize,
+    num_ones: usize,
+}
+
+impl BinaryStringInfo {
+    /// Counts the zeroes and ones in column `n` of every line.
+    fn new_for_idx(input: &PackedReport, n: usize) -> Self {
+        let (limb, mask) = bit_position(input.width, n);
+
+        #[cfg(feature = "parallel")]
+        let words = input.bits.par_chunks_exact(input.limbs);
+
+        #[cfg(not(feature = "parallel"))]
+        let words = input.bits.chunks_exact(input.limbs);
+
+        let num_ones = words.filter(|w| w[limb] & mask != 0).count();
+
+        BinaryStringInfo {
+            num_zeroes: input.len() - num_ones,
+            num_ones,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    #[test]
+    fn test_packed_report() {
+        let packed = PackedReport::parse(&["00100", "11110"]).unwrap();
+
+        assert_eq!(packed.width(), 5);
+        assert_eq!(packed.len(), 2);
+        assert_eq!(packed.bits, [0b00100, 0b11110]);
+        assert!(packed.bit(0, 2));
+        assert!(!packed.bit(1, 4));
+
+        let wide = format!("1{}1", "0".repeat(98));
+        let packed = PackedReport::parse(&[&wide]).unwrap();
+        assert_eq!(packed.bits, [1, 1 << 35]);
+        assert_eq!(packed.value(0), (BigUint::from(1u32) << 99) + 1u32);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(PackedReport::parse(&[]), Err(ReportError::Empty));
+        assert_eq!(
+            PackedReport::parse(&["0101", "011", "1111"]),
+            Err(ReportError::InconsistentWidth {
+                line: 2,
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            PackedReport::parse(&["0101", "01a1"]),
+            Err(ReportError::InvalidDigit { line: 2, column: 3 })
+        );
+        assert_eq!(
+            ReportError::InconsistentWidth {
+                line: 2,
+                expected: 4,
+                found: 3
+            }
+            .to_string(),
+            "line 2 is 3 bits wide, but the first line is 4"
+        );
+    }
+
+    #[test]
+    fn test_binary_string_info() {
+        let input = PackedReport::parse(&EXAMPLE).unwrap();
+
+        assert_eq!(
+            BinaryStringInfo::new_for_idx(&input, 0),
+            BinaryStringInfo {
+                num_zeroes: 5,
+                num_ones: 7
+            }
+        );
+        assert_eq!(
+            BinaryStringInfo::new_for_idx(&input, 4),
+            BinaryStringInfo {
+                num_zeroes: 7,
+                num_ones: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_rates() {
+        let input = PackedReport::parse(&EXAMPLE).unwrap();
+        let columns = (0..input.width())
+            .map(|i| BinaryStringInfo::new_for_idx(&input, i))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            get_rate(&columns, BitPolicy::MOST_COMMON),
+            Ok(BigUint::from(22u32))
+        );
+        assert_eq!(
+            get_rate(&columns, BitPolicy::LEAST_COMMON),
+            Ok(BigUint::from(9u32))
+        );
+    }
+
+    #[test]
+    fn test_rate_tie_breaks() {
+        // The first column is mostly ones, and the second is tied.
+        let input = PackedReport::parse(&["10", "11", "00", "11"]).unwrap();
+        let columns = (0..input.width())
+            .map(|i| BinaryStringInfo::new_for_idx(&input, i))
+            .collect::<Vec<_>>();
+
+        let cases = [
+            (BitCriteria::MostCommon, TieBreak::Zero, Ok(0b10u32)),
+            (BitCriteria::MostCommon, TieBreak::One, Ok(0b11)),
+            (
+                BitCriteria::MostCommon,
+                TieBreak::KeepBoth,
+                Err(ReportError::AmbiguousRate { column: 2 }),
+            ),
+            (
+                BitCriteria::MostCommon,
+                TieBreak::Error,
+                Err(ReportError::Tie { column: 2 }),
+            ),
+            (BitCriteria::LeastCommon, TieBreak::Zero, Ok(0b00)),
+            (BitCriteria::LeastCommon, TieBreak::One, Ok(0b01)),
+            (
+                BitCriteria::LeastCommon,
+                TieBreak::KeepBoth,
+                Err(ReportError::AmbiguousRate { column: 2 }),
+            ),
+            (
+                BitCriteria::LeastCommon,
+                TieBreak::Error,
+                Err(ReportError::Tie { column: 2 }),
+            ),
+        ];
+
+        for (criteria, tie, expected) in cases {
+            assert_eq!(
+                get_rate(&columns, BitPolicy { criteria, tie }),
+                expected.map(BigUint::from),
+                "{:?} with ties to {:?}",
+                criteria,
+                tie
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_policies() {
+        let policies = Policies {
+            co2_scrubber: BitPolicy {
+                criteria: BitCriteria::LeastCommon,
+                tie: TieBreak::Error,
+            },
+            ..Policies::default()
+        };
+
+        assert_eq!(
+            DiagnosticReport::with_policies(&EXAMPLE, &policies).unwrap_err(),
+            ReportError::Tie { column: 3 }
+        );
+        assert_eq!(
+            ReportError::AmbiguousRating { remaining: 3 }.to_string(),
+            "3 different lines are left to choose the rating from"
+        );
+    }
+
+    #[test]
+    fn test_power_consumption() {
+        let diag = DiagnosticReport::new(&EXAMPLE).unwrap();
+
+        assert_eq!(diag.power_consumption(), BigUint::from(198u32));
+    }
+
+    #[test]
+    fn test_wide_report() {
+        // The example with 65 zero columns in front, so 70 bits wide. Those
+        // columns are all zero, which makes them all ones in epsilon.
+        let lines = EXAMPLE
+            .iter()
+            .map(|l| format!("{}{}", "0".repeat(65), l))
+            .collect::<Vec<_>>();
+        let lines = lines.iter().map(String::as_str).collect::<Vec<_>>();
+        let diag = DiagnosticReport::new(&lines).unwrap();
+
+        let one = BigUint::from(1u32);
+        assert_eq!(diag.gamma_rate, BigUint::from(22u32));
+        assert_eq!(diag.epsilon_rate, (&one << 70) - (&one << 5) + 9u32);
+        assert_eq!(diag.life_support_rating(), BigUint::from(230u32));
+    }
+
+    #[test]
+    fn test_diagnostic_report_snapshot() {
+        insta::assert_debug_snapshot!(DiagnosticReport::new(&EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn test_life_support_rating() {
+        let diag = DiagnosticReport::new(&EXAMPLE).unwrap();
+
+        assert_eq!(diag.life_support_rating(), BigUint::from(230u32));
+    }
+
+    #[test]
+    fn test_parts() {
+        let report = PackedReport::parse(&EXAMPLE).unwrap();
+
+        assert_eq!(part_1(&report), Ok(BigUint::from(198u32)));
+        assert_eq!(part_2(&report), Ok(BigUint::from(230u32)));
+    }
+}
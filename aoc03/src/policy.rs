@@ -0,0 +1,174 @@
+use std::cmp::Ordering;
+
+use crate::ReportError;
+
+/// Which bit a policy prefers in a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitCriteria {
+    MostCommon,
+    LeastCommon,
+}
+
+/// Which bit a policy picks when a column has as many zeroes as ones,
+/// whatever its criteria.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    Zero,
+    One,
+    /// Keep lines with either bit. A rate holds a single bit per column, so
+    /// rates fail with `ReportError::AmbiguousRate` instead.
+    KeepBoth,
+    /// Fail with `ReportError::Tie`.
+    Error,
+}
+
+/// How a rate or rating picks a bit from each column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitPolicy {
+    pub criteria: BitCriteria,
+    pub tie: TieBreak,
+}
+
+impl BitPolicy {
+    /// The most common bit, with ties going to `1`, as for gamma and O2.
+    pub const MOST_COMMON: BitPolicy = BitPolicy {
+        criteria: BitCriteria::MostCommon,
+        tie: TieBreak::One,
+    };
+
+    /// The least common bit, with ties going to `0`, as for epsilon and CO2.
+    pub const LEAST_COMMON: BitPolicy = BitPolicy {
+        criteria: BitCriteria::LeastCommon,
+        tie: TieBreak::Zero,
+    };
+
+    /// Which bits to keep in `column` (0-based) given its counts, as
+    /// `[zero, one]`.
+    pub(crate) fn keep(
+        &self,
+        column: usize,
+        zeroes: usize,
+        ones: usize,
+    ) -> Result<[bool; 2], ReportError> {
+        let most_common_is_one = match zeroes.cmp(&ones) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => {
+                return match self.tie {
+                    TieBreak::Zero => Ok([true, false]),
+                    TieBreak::One => Ok([false, true]),
+                    TieBreak::KeepBoth => Ok([true, true]),
+                    TieBreak::Error => Err(ReportError::Tie { column: column + 1 }),
+                }
+            }
+        };
+
+        let one = match self.criteria {
+            BitCriteria::MostCommon => most_common_is_one,
+            BitCriteria::LeastCommon => !most_common_is_one,
+        };
+
+        Ok([!one, one])
+    }
+
+    /// Which bits a rating keeps in `column` (0-based), like `keep`, except
+    /// that a column where every remaining line has the same bit keeps them
+    /// all.
+    ///
+    /// The least common bit of such a column is one no line has, so keeping
+    /// only it would leave nothing to rate. The puzzle input never gets
+    /// there, but the original solution recursed forever when it did, e.g.
+    /// for the CO2 rating of `100`, `101` and `110`.
+    pub(crate) fn keep_for_rating(
+        &self,
+        column: usize,
+        zeroes: usize,
+        ones: usize,
+    ) -> Result<[bool; 2], ReportError> {
+        match (zeroes, ones) {
+            (0, _) | (_, 0) => Ok([true, true]),
+            _ => self.keep(column, zeroes, ones),
+        }
+    }
+}
+
+/// The policy behind each of a `DiagnosticReport`'s values. The default
+/// follows the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policies {
+    pub gamma: BitPolicy,
+    pub epsilon: BitPolicy,
+    pub o2_gen: BitPolicy,
+    pub co2_scrubber: BitPolicy,
+}
+
+impl Default for Policies {
+    fn default() -> Self {
+        Policies {
+            gamma: BitPolicy::MOST_COMMON,
+            epsilon: BitPolicy::LEAST_COMMON,
+            o2_gen: BitPolicy::MOST_COMMON,
+            co2_scrubber: BitPolicy::LEAST_COMMON,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TIES: [TieBreak; 4] = [
+        TieBreak::Zero,
+        TieBreak::One,
+        TieBreak::KeepBoth,
+        TieBreak::Error,
+    ];
+
+    #[test]
+    fn test_keep_untied() {
+        for tie in TIES {
+            let most = BitPolicy {
+                criteria: BitCriteria::MostCommon,
+                tie,
+            };
+            let least = BitPolicy {
+                criteria: BitCriteria::LeastCommon,
+                tie,
+            };
+
+            assert_eq!(most.keep(0, 3, 5), Ok([false, true]));
+            assert_eq!(most.keep(0, 5, 3), Ok([true, false]));
+            assert_eq!(least.keep(0, 3, 5), Ok([true, false]));
+            assert_eq!(least.keep(0, 5, 3), Ok([false, true]));
+        }
+    }
+
+    #[test]
+    fn test_keep_tied() {
+        for criteria in [BitCriteria::MostCommon, BitCriteria::LeastCommon] {
+            let keep = |tie| BitPolicy { criteria, tie }.keep(2, 4, 4);
+
+            assert_eq!(keep(TieBreak::Zero), Ok([true, false]));
+            assert_eq!(keep(TieBreak::One), Ok([false, true]));
+            assert_eq!(keep(TieBreak::KeepBoth), Ok([true, true]));
+            assert_eq!(keep(TieBreak::Error), Err(ReportError::Tie { column: 3 }));
+        }
+    }
+
+    #[test]
+    fn test_keep_for_rating_shared_bit() {
+        for criteria in [BitCriteria::MostCommon, BitCriteria::LeastCommon] {
+            for tie in TIES {
+                let policy = BitPolicy { criteria, tie };
+
+                assert_eq!(policy.keep_for_rating(0, 0, 3), Ok([true, true]));
+                assert_eq!(policy.keep_for_rating(0, 3, 0), Ok([true, true]));
+            }
+        }
+
+        assert_eq!(
+            BitPolicy::LEAST_COMMON.keep_for_rating(0, 2, 1),
+            Ok([false, true])
+        );
+    }
+}
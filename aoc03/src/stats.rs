@@ -0,0 +1,262 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{BinaryStringInfo, PackedReport};
+
+/// Per-column statistics over a report's bits. Column numbers are 1-based,
+/// counting from the left.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ColumnStats {
+    pub lines: usize,
+    pub columns: Vec<Column>,
+    /// `correlations[i][j]` is the phi coefficient between columns `i + 1`
+    /// and `j + 1`, from -1 when one is always the other inverted to 1 when
+    /// they always match. It's `None` if either column never changes. Only
+    /// filled in by `with_correlations`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlations: Option<Vec<Vec<Option<f64>>>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Column {
+    pub column: usize,
+    pub zeroes: usize,
+    pub ones: usize,
+    /// How far the column is from an even split, as a percentage of the
+    /// lines: 0 when it's even and 100 when every line has the same bit.
+    pub bias: f64,
+    /// The Shannon entropy of the column's bit, in bits.
+    pub entropy: f64,
+}
+
+impl ColumnStats {
+    pub fn new(report: &PackedReport) -> Self {
+        let lines = report.len();
+
+        let columns = (0..report.width())
+            .map(|i| {
+                let BinaryStringInfo {
+                    num_zeroes: zeroes,
+                    num_ones: ones,
+                } = BinaryStringInfo::new_for_idx(report, i);
+
+                Column {
+                    column: i + 1,
+                    zeroes,
+                    ones,
+                    bias: zeroes.abs_diff(ones) as f64 / lines as f64 * 100.0,
+                    entropy: entropy(ones as f64 / lines as f64),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        ColumnStats {
+            lines,
+            columns,
+            correlations: None,
+        }
+    }
+
+    /// Like `new`, but also correlates every pair of columns. That takes
+    /// O(lines * width²) time and a width × width table, so it's best kept
+    /// to narrow reports.
+    pub fn with_correlations(report: &PackedReport) -> Self {
+        let mut stats = ColumnStats::new(report);
+        let columns = &stats.columns;
+
+        // How many lines have a 1 in both columns, for every pair.
+        let mut both = vec![vec![0usize; columns.len()]; columns.len()];
+        let mut set = Vec::with_capacity(columns.len());
+
+        for line in 0..stats.lines {
+            set.clear();
+            set.extend((0..report.width()).filter(|&column| report.bit(line, column)));
+
+            for &i in &set {
+                for &j in &set {
+                    both[i][j] += 1;
+                }
+            }
+        }
+
+        let correlations = columns
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                columns
+                    .iter()
+                    .enumerate()
+                    .map(|(j, b)| phi(a, b, both[i][j], stats.lines))
+                    .collect()
+            })
+            .collect();
+
+        stats.correlations = Some(correlations);
+        stats
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// The entropy of a bit that is 1 with probability `p`.
+fn entropy(p: f64) -> f64 {
+    let entropy: f64 = [p, 1.0 - p]
+        .into_iter()
+        .filter(|&p| p > 0.0)
+        .map(|p| -p * p.log2())
+        .sum();
+
+    // A constant column sums to -0.0, which would print as "-0.000".
+    entropy + 0.0
+}
+
+/// The phi coefficient of two columns, given how many of the `lines` have a
+/// 1 in both.
+fn phi(a: &Column, b: &Column, both: usize, lines: usize) -> Option<f64> {
+    let denominator = (a.zeroes as f64 * a.ones as f64 * b.zeroes as f64 * b.ones as f64).sqrt();
+
+    if denominator == 0.0 {
+        return None;
+    }
+
+    let ones_ones = both as f64;
+    let ones_zeroes = (a.ones - both) as f64;
+    let zeroes_ones = (b.ones - both) as f64;
+    let zeroes_zeroes = (lines + both - a.ones - b.ones) as f64;
+
+    Some((ones_ones * zeroes_zeroes - ones_zeroes * zeroes_ones) / denominator)
+}
+
+impl fmt::Display for ColumnStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Lines: {}", self.lines)?;
+        writeln!(f, "Column  Zeroes    Ones    Bias  Entropy")?;
+        for column in &self.columns {
+            writeln!(
+                f,
+                "{:>6}  {:>6}  {:>6}  {:>5.1}%  {:>7.3}",
+                column.column, column.zeroes, column.ones, column.bias, column.entropy
+            )?;
+        }
+
+        let Some(correlations) = &self.correlations else {
+            return Ok(());
+        };
+
+        writeln!(f, "Correlation:")?;
+        write!(f, "{:>6}", "")?;
+        for column in &self.columns {
+            write!(f, "  {:>5}", column.column)?;
+        }
+        writeln!(f)?;
+
+        for (column, row) in self.columns.iter().zip(correlations) {
+            write!(f, "{:>6}", column.column)?;
+            for phi in row {
+                match phi {
+                    Some(phi) => write!(f, "  {:>5.2}", phi)?,
+                    None => write!(f, "  {:>5}", "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    fn stats(lines: &[&str]) -> ColumnStats {
+        ColumnStats::new(&PackedReport::parse(lines).unwrap())
+    }
+
+    #[test]
+    fn test_columns() {
+        let stats = stats(&EXAMPLE);
+
+        assert_eq!(stats.lines, 12);
+        assert_eq!(
+            stats
+                .columns
+                .iter()
+                .map(|c| (c.zeroes, c.ones))
+                .collect::<Vec<_>>(),
+            [(5, 7), (7, 5), (4, 8), (5, 7), (7, 5)]
+        );
+
+        let first = &stats.columns[0];
+        assert!((first.bias - 2.0 / 12.0 * 100.0).abs() < 1e-9);
+        assert!((first.entropy - 0.979_868_756).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bias_and_entropy_extremes() {
+        let even = &stats(&["01", "01", "11", "10"]).columns[0];
+        assert_eq!(even.bias, 0.0);
+        assert_eq!(even.entropy, 1.0);
+
+        let constant = &stats(&["10", "11", "10"]).columns[0];
+        assert_eq!(constant.bias, 100.0);
+        assert_eq!(constant.entropy, 0.0);
+        assert!(constant.entropy.is_sign_positive());
+    }
+
+    #[test]
+    fn test_correlations() {
+        // The second column is the first inverted, the third matches the
+        // first, the fourth is unrelated and the fifth never changes.
+        let report = PackedReport::parse(&["10101", "01001", "10111", "01011"]).unwrap();
+        assert_eq!(ColumnStats::new(&report).correlations, None);
+
+        let stats = ColumnStats::with_correlations(&report);
+        let correlations = stats.correlations.unwrap();
+        let phi = |i: usize, j: usize| correlations[i - 1][j - 1];
+
+        assert_eq!(phi(1, 1), Some(1.0));
+        assert_eq!(phi(1, 2), Some(-1.0));
+        assert_eq!(phi(1, 3), Some(1.0));
+        assert_eq!(phi(2, 3), Some(-1.0));
+        assert_eq!(phi(1, 4), Some(0.0));
+        assert_eq!(phi(4, 1), Some(0.0));
+        assert_eq!(phi(1, 5), None);
+        assert_eq!(phi(5, 5), None);
+    }
+
+    #[test]
+    fn test_column_stats_table() {
+        let report = PackedReport::parse(&EXAMPLE).unwrap();
+
+        insta::assert_snapshot!(ColumnStats::with_correlations(&report).to_string());
+        assert!(!ColumnStats::new(&report)
+            .to_string()
+            .contains("Correlation"));
+    }
+
+    #[test]
+    fn test_column_stats_json() {
+        let report = PackedReport::parse(&EXAMPLE).unwrap();
+        let json = |stats: ColumnStats| {
+            serde_json::from_str::<serde_json::Value>(&stats.to_json()).unwrap()
+        };
+
+        let plain = json(ColumnStats::new(&report));
+        assert_eq!(plain["lines"], 12);
+        assert_eq!(plain["columns"][2]["column"], 3);
+        assert_eq!(plain["columns"][2]["ones"], 8);
+        assert!(plain.get("correlations").is_none());
+
+        let correlated = json(ColumnStats::with_correlations(&report));
+        assert_eq!(correlated["correlations"][0][0], 1.0);
+    }
+}
@@ -0,0 +1,388 @@
+use num_bigint::BigUint;
+
+use crate::{BitPolicy, PackedReport, ReportError};
+
+/// A binary trie over a report's lines, where every node counts the lines
+/// passing through it. The root branches on column 0. A node only branches
+/// once a second line reaches it; until then it just remembers which line
+/// it holds, so random lines don't each grow a long chain of nodes.
+#[derive(Debug, Clone)]
+pub struct BitTrie<'a> {
+    report: &'a PackedReport,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Indices into `nodes` of the `0` and `1` children. The root is never
+    /// anyone's child, so 0 means there isn't one.
+    children: [usize; 2],
+    count: usize,
+    /// The first line to reach this node, which is the only one if `count`
+    /// is 1.
+    line: usize,
+}
+
+impl<'a> BitTrie<'a> {
+    pub fn new(report: &'a PackedReport) -> Self {
+        let mut trie = BitTrie {
+            report,
+            nodes: vec![Node::default()],
+        };
+
+        for line in 0..report.len() {
+            trie.insert(line);
+        }
+
+        trie
+    }
+
+    fn insert(&mut self, line: usize) {
+        let mut node = 0;
+
+        for column in 0..=self.report.width() {
+            self.nodes[node].count += 1;
+
+            match self.nodes[node].count {
+                1 => {
+                    self.nodes[node].line = line;
+                    return;
+                }
+                // Lines that reach a leaf are all identical.
+                _ if column == self.report.width() => return,
+                // The line held here has company now, so move it down.
+                2 => {
+                    let held = self.nodes[node].line;
+                    let child = self.child_or_insert(node, self.report.bit(held, column));
+                    self.nodes[child].count = 1;
+                    self.nodes[child].line = held;
+                }
+                _ => {}
+            }
+
+            node = self.child_or_insert(node, self.report.bit(line, column));
+        }
+    }
+
+    fn child_or_insert(&mut self, node: usize, one: bool) -> usize {
+        if self.nodes[node].children[one as usize] == 0 {
+            self.nodes.push(Node::default());
+            self.nodes[node].children[one as usize] = self.nodes.len() - 1;
+        }
+
+        self.nodes[node].children[one as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How many lines start with `prefix`, a string of `0`s and `1`s.
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        let mut node = 0;
+
+        for (column, c) in prefix.bytes().enumerate() {
+            let one = match c {
+                b'0' => false,
+                b'1' => true,
+                _ => return 0,
+            };
+
+            if column >= self.report.width() {
+                return 0;
+            }
+
+            let n = &self.nodes[node];
+
+            // At most one line is left, so the rest of the prefix has to
+            // match it.
+            if n.count <= 1 {
+                if n.count == 0 || self.report.bit(n.line, column) != one {
+                    return 0;
+                }
+                continue;
+            }
+
+            node = n.children[one as usize];
+            if node == 0 {
+                return 0;
+            }
+        }
+
+        self.nodes[node].count
+    }
+
+    /// Walks from the root towards the leaves, keeping the bits `policy`
+    /// picks among the lines left at each step, until only one line is
+    /// left, and returns its value. Keeping both bits on a tie means
+    /// following every branch, so it fails if several different lines are
+    /// left at the end.
+    pub fn rating(&self, policy: BitPolicy) -> Result<BigUint, ReportError> {
+        if self.is_empty() {
+            return Ok(BigUint::default());
+        }
+
+        let mut frontier = vec![0];
+        let mut next = Vec::new();
+
+        for column in 0..self.report.width() {
+            if let [node] = frontier[..] {
+                if self.nodes[node].count == 1 {
+                    break;
+                }
+            }
+
+            let mut counts = [0; 2];
+            for &node in &frontier {
+                let n = &self.nodes[node];
+
+                if n.count == 1 {
+                    counts[self.report.bit(n.line, column) as usize] += 1;
+                } else {
+                    for (count, &child) in counts.iter_mut().zip(&n.children) {
+                        *count += self.count(child);
+                    }
+                }
+            }
+
+            let [zeroes, ones] = counts;
+            let keep = policy.keep_for_rating(column, zeroes, ones)?;
+
+            for &node in &frontier {
+                let n = &self.nodes[node];
+
+                if n.count == 1 {
+                    if keep[self.report.bit(n.line, column) as usize] {
+                        next.push(node);
+                    }
+                } else {
+                    next.extend(
+                        n.children
+                            .iter()
+                            .zip(keep)
+                            .filter(|&(&child, keep)| keep && child != 0)
+                            .map(|(&child, _)| child),
+                    );
+                }
+            }
+
+            std::mem::swap(&mut frontier, &mut next);
+            next.clear();
+        }
+
+        // Different nodes hold different lines, and the lines under one
+        // node are identical once it's a leaf.
+        match frontier[..] {
+            [node] => Ok(self.report.value(self.nodes[node].line)),
+            _ => Err(ReportError::AmbiguousRating {
+                remaining: frontier.iter().map(|&node| self.nodes[node].count).sum(),
+            }),
+        }
+    }
+
+    /// The `len`-bit prefix shared by the most lines, with how many share
+    /// it. Ties go to the lowest prefix. `None` if `len` is wider than the
+    /// report or there are no lines.
+    pub fn most_common_prefix(&self, len: usize) -> Option<(String, usize)> {
+        self.prefixes(len)
+            .into_iter()
+            .rev()
+            .max_by_key(|&(_, count)| count)
+    }
+
+    /// The `len`-bit prefix shared by the fewest lines, ignoring prefixes
+    /// no line has, with how many share it. Ties go to the lowest prefix.
+    pub fn least_common_prefix(&self, len: usize) -> Option<(String, usize)> {
+        self.prefixes(len)
+            .into_iter()
+            .min_by_key(|&(_, count)| count)
+    }
+
+    /// Every `len`-bit prefix some line has, in ascending order, with how
+    /// many lines have it.
+    fn prefixes(&self, len: usize) -> Vec<(String, usize)> {
+        let mut prefixes = Vec::new();
+
+        if len <= self.report.width() && !self.is_empty() {
+            self.collect_prefixes(0, &mut String::with_capacity(len), len, &mut prefixes);
+        }
+
+        prefixes
+    }
+
+    fn collect_prefixes(
+        &self,
+        node: usize,
+        prefix: &mut String,
+        len: usize,
+        prefixes: &mut Vec<(String, usize)>,
+    ) {
+        let n = &self.nodes[node];
+
+        if prefix.len() == len {
+            prefixes.push((prefix.clone(), n.count));
+            return;
+        }
+
+        if n.count == 1 {
+            let mut prefix = prefix.clone();
+            for column in prefix.len()..len {
+                prefix.push(bit_char(self.report.bit(n.line, column)));
+            }
+            prefixes.push((prefix, 1));
+            return;
+        }
+
+        for (bit, &child) in n.children.iter().enumerate() {
+            if child != 0 {
+                prefix.push(bit_char(bit == 1));
+                self.collect_prefixes(child, prefix, len, prefixes);
+                prefix.pop();
+            }
+        }
+    }
+
+    fn count(&self, child: usize) -> usize {
+        match child {
+            0 => 0,
+            _ => self.nodes[child].count,
+        }
+    }
+}
+
+fn bit_char(one: bool) -> char {
+    if one {
+        '1'
+    } else {
+        '0'
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BitCriteria, TieBreak};
+
+    const EXAMPLE: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    fn packed(lines: &[&str]) -> PackedReport {
+        PackedReport::parse(lines).unwrap()
+    }
+
+    #[test]
+    fn test_count_prefix() {
+        let report = packed(&EXAMPLE);
+        let trie = BitTrie::new(&report);
+
+        assert_eq!(trie.len(), 12);
+        assert_eq!(trie.count_prefix(""), 12);
+        assert_eq!(trie.count_prefix("1"), 7);
+        assert_eq!(trie.count_prefix("10"), 4);
+        assert_eq!(trie.count_prefix("10110"), 1);
+        assert_eq!(trie.count_prefix("000"), 1);
+        assert_eq!(trie.count_prefix("0110"), 0);
+        assert_eq!(trie.count_prefix("101100"), 0);
+        assert_eq!(trie.count_prefix("1x"), 0);
+        // Only one line starts with 000, so the rest is matched against it.
+        assert_eq!(trie.count_prefix("00010"), 1);
+        assert_eq!(trie.count_prefix("00011"), 0);
+    }
+
+    #[test]
+    fn test_o2_gen_rating() {
+        assert_eq!(
+            BitTrie::new(&packed(&EXAMPLE)).rating(BitPolicy::MOST_COMMON),
+            Ok(BigUint::from(23u32))
+        );
+    }
+
+    #[test]
+    fn test_co2_scrubber_rating() {
+        assert_eq!(
+            BitTrie::new(&packed(&EXAMPLE)).rating(BitPolicy::LEAST_COMMON),
+            Ok(BigUint::from(10u32))
+        );
+    }
+
+    #[test]
+    fn test_co2_scrubber_rating_shared_bit() {
+        assert_eq!(
+            BitTrie::new(&packed(&["100", "101", "110"])).rating(BitPolicy::LEAST_COMMON),
+            Ok(BigUint::from(6u32))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_lines() {
+        let report = packed(&["011", "011", "100"]);
+        let trie = BitTrie::new(&report);
+
+        assert_eq!(trie.count_prefix("011"), 2);
+        assert_eq!(trie.rating(BitPolicy::MOST_COMMON), Ok(BigUint::from(3u32)));
+        assert_eq!(
+            trie.rating(BitPolicy::LEAST_COMMON),
+            Ok(BigUint::from(4u32))
+        );
+    }
+
+    #[test]
+    fn test_rating_tie_breaks() {
+        // Both columns are tied, and the second one again after keeping
+        // the zeroes of the first.
+        let report = packed(&["00", "01", "10", "10"]);
+        let trie = BitTrie::new(&report);
+
+        let cases = [
+            (BitCriteria::MostCommon, TieBreak::Zero, Ok(0b00u32)),
+            (BitCriteria::MostCommon, TieBreak::One, Ok(0b10)),
+            (
+                BitCriteria::MostCommon,
+                TieBreak::KeepBoth,
+                Err(ReportError::AmbiguousRating { remaining: 3 }),
+            ),
+            (
+                BitCriteria::MostCommon,
+                TieBreak::Error,
+                Err(ReportError::Tie { column: 1 }),
+            ),
+            (BitCriteria::LeastCommon, TieBreak::Zero, Ok(0b00)),
+            (BitCriteria::LeastCommon, TieBreak::One, Ok(0b10)),
+            (BitCriteria::LeastCommon, TieBreak::KeepBoth, Ok(0b01)),
+            (
+                BitCriteria::LeastCommon,
+                TieBreak::Error,
+                Err(ReportError::Tie { column: 1 }),
+            ),
+        ];
+
+        for (criteria, tie, expected) in cases {
+            assert_eq!(
+                trie.rating(BitPolicy { criteria, tie }),
+                expected.map(BigUint::from),
+                "{:?} with ties to {:?}",
+                criteria,
+                tie
+            );
+        }
+    }
+
+    #[test]
+    fn test_common_prefixes() {
+        let report = packed(&EXAMPLE);
+        let trie = BitTrie::new(&report);
+
+        assert_eq!(trie.most_common_prefix(0), Some((String::new(), 12)));
+        assert_eq!(trie.most_common_prefix(2), Some(("10".to_string(), 4)));
+        assert_eq!(trie.least_common_prefix(2), Some(("01".to_string(), 2)));
+        assert_eq!(trie.most_common_prefix(3), Some(("101".to_string(), 3)));
+        assert_eq!(trie.least_common_prefix(3), Some(("000".to_string(), 1)));
+        assert_eq!(trie.most_common_prefix(6), None);
+    }
+}
@@ -0,0 +1,68 @@
+//! Compare the serial and parallel board search with:
+//!
+//! ```sh
+//! cargo bench --bench run -- --save-baseline serial
+//! cargo bench --bench run --features parallel -- --baseline serial
+//! ```
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use aoc04::GameState;
+
+/// Generates a game with `num_boards` boards whose numbers are drawn from
+/// `[0, max)`, using a fixed-seed LCG so that every run benchmarks the same
+/// input. Every number in the range is eventually drawn.
+fn generate_input(num_boards: usize, max: u32) -> String {
+    let mut state: u64 = 0x2021_0004;
+    let mut next = |bound: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % bound as u64) as usize
+    };
+
+    let mut shuffle = |nums: &mut Vec<u32>| {
+        for i in (1..nums.len()).rev() {
+            nums.swap(i, next(i + 1));
+        }
+    };
+
+    let mut draws = (0..max).collect::<Vec<_>>();
+    shuffle(&mut draws);
+
+    let mut input = draws
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    input.push('\n');
+
+    for _ in 0..num_boards {
+        let mut nums = (0..max).collect::<Vec<_>>();
+        shuffle(&mut nums);
+
+        input.push('\n');
+        for row in nums[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>();
+            input.push_str(&row.join(" "));
+            input.push('\n');
+        }
+    }
+
+    input
+}
+
+fn bench_run(c: &mut Criterion) {
+    let input = generate_input(2_000, 1_000);
+
+    c.bench_function("run 2k boards", |b| {
+        b.iter_batched(
+            || GameState::new(&input),
+            |mut game| game.run(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_run);
+criterion_main!(benches);
@@ -1,8 +1,13 @@
 use std::collections::HashSet;
 
+use aoc::arith;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 use crate::{
     board::{create_virtual_board, Board},
     parse::parse_draws_and_boards,
+    OverflowError,
 };
 
 pub struct GameState {
@@ -10,7 +15,11 @@ pub struct GameState {
     pub boards: Vec<Board<u32>>,
     pub virtual_boards: Vec<Board<bool>>,
     pub completed_boards: HashSet<usize>,
+    /// Board indices in the order they won, parallel to `winning_scores`.
+    pub completion_order: Vec<usize>,
     pub winning_scores: Vec<u32>,
+    /// The index into `draws` of the next number to draw.
+    next_draw: usize,
 }
 
 impl GameState {
@@ -28,19 +37,34 @@ impl GameState {
             boards,
             virtual_boards,
             completed_boards: HashSet::new(),
+            completion_order: Vec::new(),
             winning_scores: Vec::new(),
+            next_draw: 0,
         }
     }
 
-    pub fn run(&mut self) {
-        for drawn in self.draws.iter() {
-            for (b, board) in self.boards.iter().enumerate() {
-                if let Some((i, j)) = board.find(*drawn) {
+    pub fn run(&mut self) -> Result<(), OverflowError> {
+        self.run_until(self.boards.len())
+    }
+
+    /// Draws numbers until at least `winners` boards have won or the draws
+    /// run out. Calling it again carries on from the next draw.
+    pub fn run_until(&mut self, winners: usize) -> Result<(), OverflowError> {
+        while self.winning_scores.len() < winners {
+            let Some(&drawn) = self.draws.get(self.next_draw) else {
+                break;
+            };
+            self.next_draw += 1;
+
+            for (b, found) in self.find_on_boards(drawn).into_iter().enumerate() {
+                if let Some((i, j)) = found {
                     self.virtual_boards[b].set(i, j, true);
 
                     if !self.completed_boards.contains(&b) && self.virtual_boards[b].is_winner() {
                         self.completed_boards.insert(b);
-                        self.winning_scores.push(self.score_for_board(b) * drawn);
+                        self.completion_order.push(b);
+                        let score = arith::mul(self.score_for_board(b)?, drawn)?;
+                        self.winning_scores.push(score);
 
                         if self.completed_boards.len() == self.boards.len() {
                             // Stop as soon as the last board has won
@@ -50,9 +74,23 @@ impl GameState {
                 }
             }
         }
+
+        Ok(())
     }
 
-    fn score_for_board(&self, board_num: usize) -> u32 {
+    /// Locates `drawn` on every board. With the `parallel` feature the boards
+    /// are searched concurrently, but the results are always in board order.
+    fn find_on_boards(&self, drawn: u32) -> Vec<Option<(usize, usize)>> {
+        #[cfg(feature = "parallel")]
+        let boards = self.boards.par_iter();
+
+        #[cfg(not(feature = "parallel"))]
+        let boards = self.boards.iter();
+
+        boards.map(|board| board.find(drawn)).collect()
+    }
+
+    fn score_for_board(&self, board_num: usize) -> Result<u32, OverflowError> {
         let board = &self.boards[board_num];
         let virtual_board = &self.virtual_boards[board_num];
 
@@ -61,12 +99,12 @@ impl GameState {
         for (i, row) in board.rows().iter().enumerate() {
             for (j, item) in row.iter().enumerate() {
                 if !virtual_board.has_marked(i, j) {
-                    sum += *item
+                    sum = arith::add(sum, *item)?;
                 }
             }
         }
 
-        sum
+        Ok(sum)
     }
 }
 
@@ -99,9 +137,35 @@ mod test {
 "#,
         );
 
-        game.run();
+        game.run_until(1).unwrap();
+        assert_eq!(game.winning_scores, [4512]);
+
+        game.run().unwrap();
 
         assert_eq!(game.winning_scores.first(), Some(&4512));
         assert_eq!(game.winning_scores.last(), Some(&1924));
+
+        // (board, score) for each board, in the order they won.
+        insta::assert_debug_snapshot!(game
+            .completion_order
+            .iter()
+            .zip(game.winning_scores.iter())
+            .collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_overflow() {
+        let mut game = GameState::new(
+            r#"1,2,3,4,5
+
+1 2 3 4 5
+4000000000 4000000000 4000000000 4000000000 4000000000
+4000000000 4000000000 4000000000 4000000000 4000000000
+4000000000 4000000000 4000000000 4000000000 4000000000
+4000000000 4000000000 4000000000 4000000000 4000000000
+"#,
+        );
+
+        assert_eq!(game.run(), Err(OverflowError));
     }
 }
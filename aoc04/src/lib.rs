@@ -0,0 +1,6 @@
+pub use aoc::arith::OverflowError;
+pub use game_state::GameState;
+
+pub mod board;
+pub mod game_state;
+mod parse;
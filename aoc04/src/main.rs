@@ -1,15 +1,16 @@
-use game_state::GameState;
-
-mod board;
-mod game_state;
-mod parse;
+use aoc::timing::timed;
+use aoc04::{GameState, OverflowError};
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn main() {
+fn main() -> Result<(), OverflowError> {
     let mut game = GameState::new(INPUT);
-    game.run();
 
+    timed("Part 1", || game.run_until(1))?;
     println!("Part 1: {}", game.winning_scores.first().unwrap());
+
+    timed("Part 2", || game.run())?;
     println!("Part 2: {}", game.winning_scores.last().unwrap());
+
+    Ok(())
 }
@@ -0,0 +1,52 @@
+//! Compare the serial and parallel segment expansion with:
+//!
+//! ```sh
+//! cargo bench --bench overlaps -- --save-baseline serial
+//! cargo bench --bench overlaps --features parallel -- --baseline serial
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc05::{parse_input, part_2};
+
+/// Generates `n` horizontal, vertical and diagonal segments on a
+/// `size` x `size` grid using a fixed-seed LCG, so that every run benchmarks
+/// the same input.
+fn generate_input(n: usize, size: u32) -> String {
+    let mut state: u64 = 0x2021_0005;
+    let mut next = |bound: u32| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % bound as u64) as u32
+    };
+
+    (0..n)
+        .map(|_| {
+            let (x1, y1) = (next(size), next(size));
+            let len = next(size);
+
+            let (x2, y2) = match next(3) {
+                0 => ((x1 + len) % size, y1),
+                1 => (x1, (y1 + len) % size),
+                _ => {
+                    let len = len.min(size - 1 - x1).min(size - 1 - y1);
+                    (x1 + len, y1 + len)
+                }
+            };
+
+            format!("{},{} -> {},{}\n", x1, y1, x2, y2)
+        })
+        .collect()
+}
+
+fn bench_part_2(c: &mut Criterion) {
+    let input = parse_input(&generate_input(5_000, 1_000));
+
+    c.bench_function("part_2 5k segments", |b| {
+        b.iter(|| part_2(black_box(&input)))
+    });
+}
+
+criterion_group!(benches, bench_part_2);
+criterion_main!(benches);
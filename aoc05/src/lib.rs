@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use line_segment::{parse_line_segment, LineSegment, Orientation, Point};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub mod line_segment;
+
+pub fn parse_input(input: &str) -> Vec<LineSegment> {
+    input.lines().map(parse_line_segment).collect()
+}
+
+pub fn part_1(input: &[LineSegment]) -> usize {
+    let points = expand_points(input, |ls| {
+        ls.orientation == Orientation::Horizontal || ls.orientation == Orientation::Vertical
+    });
+
+    find_overlapped_points(points)
+}
+
+pub fn part_2(input: &[LineSegment]) -> usize {
+    let points = expand_points(input, |_| true);
+
+    find_overlapped_points(points)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn expand_points<F>(input: &[LineSegment], keep: F) -> impl Iterator<Item = Point> + '_
+where
+    F: Fn(&LineSegment) -> bool + Sync + 'static,
+{
+    input
+        .iter()
+        .filter(move |ls| keep(ls))
+        .flat_map(|ls| ls.points())
+}
+
+#[cfg(feature = "parallel")]
+fn expand_points<F>(input: &[LineSegment], keep: F) -> impl Iterator<Item = Point> + '_
+where
+    F: Fn(&LineSegment) -> bool + Sync + 'static,
+{
+    // Segments are expanded in parallel, but collected in their original order
+    // so that counting sees exactly the same points as the serial version.
+    input
+        .par_iter()
+        .filter(|ls| keep(ls))
+        .flat_map_iter(|ls| ls.points())
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Renders how many line segments cover each point, in the style of the puzzle
+/// description: `.` where no segment passes, otherwise the number of segments.
+pub fn render_overlap_map(input: &[LineSegment]) -> String {
+    let contained_points = count_points(input.iter().flat_map(|ls| ls.points()));
+
+    let width = contained_points.keys().map(|p| p.0 + 1).max().unwrap_or(0);
+    let height = contained_points.keys().map(|p| p.1 + 1).max().unwrap_or(0);
+
+    let mut map = String::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            map.push(match contained_points.get(&Point(x, y)) {
+                Some(&count) => u32::try_from(count)
+                    .ok()
+                    .and_then(|count| char::from_digit(count, 10))
+                    .unwrap_or('#'),
+                None => '.',
+            });
+        }
+        map.push('\n');
+    }
+
+    map
+}
+
+fn find_overlapped_points<P: Iterator<Item = Point>>(points: P) -> usize {
+    let mut contained_points = count_points(points);
+
+    contained_points.retain(|_, count| *count >= 2);
+
+    contained_points.keys().len()
+}
+
+fn count_points<P: Iterator<Item = Point>>(points: P) -> HashMap<Point, usize> {
+    let mut contained_points = HashMap::new();
+
+    for point in points {
+        let prev_point = contained_points.entry(point).or_insert(0);
+        *prev_point += 1;
+    }
+
+    contained_points
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let input = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+        let input_parsed = parse_input(input);
+
+        assert_eq!(part_1(&input_parsed), 5);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+        let input_parsed = parse_input(input);
+
+        assert_eq!(part_2(&input_parsed), 12);
+    }
+
+    #[test]
+    fn test_render_overlap_map() {
+        let input = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+        let input_parsed = parse_input(input);
+
+        insta::assert_snapshot!(render_overlap_map(&input_parsed));
+    }
+}
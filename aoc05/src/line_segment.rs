@@ -7,7 +7,7 @@ use nom::{
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Point(u32, u32);
+pub struct Point(pub(crate) u32, pub(crate) u32);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Orientation {
@@ -69,34 +69,14 @@ impl LineSegment {
                     .map(|y| Point(x1, y))
                     .collect()
             }
-            Orientation::DiagonalPrimary => {
-                let mut x = x1;
-                let mut y = y1 as i32;
-                let mut points = vec![];
-
-                while (x <= x2) && (y as u32 >= y2) {
-                    points.push(Point(x, y as u32));
-
-                    x += 1;
-                    y -= 1;
-                }
-
-                points
-            }
-            Orientation::DiagonalSecondary => {
-                let mut x = x1;
-                let mut y = y1;
-                let mut points = vec![];
-
-                while (x <= x2) && (y <= y2) {
-                    points.push(Point(x, y));
-
-                    x += 1;
-                    y += 1;
-                }
-
-                points
-            }
+            // Offsets from the start always stay between the endpoints, so
+            // unlike stepping x and y one at a time, nothing can overflow.
+            Orientation::DiagonalPrimary => (0..=(x2 - x1).min(y1 - y2))
+                .map(|i| Point(x1 + i, y1 - i))
+                .collect(),
+            Orientation::DiagonalSecondary => (0..=(x2 - x1).min(y2 - y1))
+                .map(|i| Point(x1 + i, y1 + i))
+                .collect(),
         }
     }
 }
@@ -212,4 +192,18 @@ mod test {
             vec![Point(7, 9), Point(8, 8), Point(9, 7)]
         );
     }
+
+    #[test]
+    fn test_points_diagonal_at_coordinate_limits() {
+        let max = u32::MAX;
+
+        assert_eq!(
+            LineSegment::new((Point(max - 2, 2), Point(max, 0))).points(),
+            vec![Point(max - 2, 2), Point(max - 1, 1), Point(max, 0)]
+        );
+        assert_eq!(
+            LineSegment::new((Point(max, max), Point(max - 1, max - 1))).points(),
+            vec![Point(max - 1, max - 1), Point(max, max)]
+        );
+    }
 }
@@ -1,33 +1,38 @@
-fn main() {
+use aoc::arith::{self, OverflowError};
+use aoc::timing::timed;
+
+fn main() -> Result<(), OverflowError> {
     let input = include_str!("../input.txt")
         .trim()
         .split(',')
         .map(|n| n.parse::<u32>().unwrap())
         .collect::<Vec<_>>();
 
-    println!("Part 1: {}", part_1(&input));
-    println!("Part 2: {}", part_2(&input));
+    println!("Part 1: {}", timed("Part 1", || part_1(&input))?);
+    println!("Part 2: {}", timed("Part 2", || part_2(&input))?);
+
+    Ok(())
 }
 
-fn part_1(input: &[u32]) -> usize {
+fn part_1(input: &[u32]) -> Result<usize, OverflowError> {
     fish_population_after_days(input, 80)
 }
 
-fn part_2(input: &[u32]) -> usize {
+fn part_2(input: &[u32]) -> Result<usize, OverflowError> {
     fish_population_after_days(input, 256)
 }
 
-fn fish_population_after_days(input: &[u32], num_days: u32) -> usize {
+fn fish_population_after_days(input: &[u32], num_days: u32) -> Result<usize, OverflowError> {
     // 8-element array, where the element at a given index represents the number of fish
     // at that age.
     // i.e., the value at index 2 represents the number of fish with age 2.
     let mut age_index = get_age_index(input);
 
     for _ in 0..num_days {
-        tick(&mut age_index);
+        tick(&mut age_index)?;
     }
 
-    age_index.iter().sum()
+    age_index.iter().try_fold(0, |acc, &n| arith::add(acc, n))
 }
 
 fn get_age_index(input: &[u32]) -> [usize; 9] {
@@ -35,18 +40,20 @@ fn get_age_index(input: &[u32]) -> [usize; 9] {
 
     for &n in input.iter() {
         let n = n as usize;
-        age_index[n] = age_index[n] + 1;
+        age_index[n] += 1;
     }
 
     age_index
 }
 
-fn tick(age_index: &mut [usize; 9]) {
+fn tick(age_index: &mut [usize; 9]) -> Result<(), OverflowError> {
     let num_spawns = age_index[0];
 
     age_index.rotate_left(1);
 
-    age_index[6] = age_index[6] + num_spawns;
+    age_index[6] = arith::add(age_index[6], num_spawns)?;
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -56,8 +63,8 @@ mod test {
     #[test]
     fn test_fish_population() {
         let input = vec![3, 4, 3, 1, 2];
-        assert_eq!(fish_population_after_days(&input, 18), 26);
-        assert_eq!(fish_population_after_days(&input, 80), 5934);
+        assert_eq!(fish_population_after_days(&input, 18), Ok(26));
+        assert_eq!(fish_population_after_days(&input, 80), Ok(5934));
     }
 
     #[test]
@@ -71,7 +78,28 @@ mod test {
     #[test]
     fn test_tick() {
         let mut age_index = [0, 1, 1, 2, 1, 0, 0, 0, 0];
-        tick(&mut age_index);
+        tick(&mut age_index).unwrap();
         assert_eq!(age_index, [1, 1, 2, 1, 0, 0, 0, 0, 0]);
     }
+
+    #[test]
+    fn test_age_index_snapshot() {
+        let mut age_index = get_age_index(&[3, 4, 3, 1, 2]);
+
+        let mut history = format!("day  0: {:?}\n", age_index);
+        for day in 1..=18 {
+            tick(&mut age_index).unwrap();
+            history.push_str(&format!("day {:>2}: {:?}\n", day, age_index));
+        }
+
+        insta::assert_snapshot!(history);
+    }
+
+    #[test]
+    fn test_fish_population_overflow() {
+        assert_eq!(
+            fish_population_after_days(&[3, 4, 3, 1, 2], 1000),
+            Err(OverflowError)
+        );
+    }
 }
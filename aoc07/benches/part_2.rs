@@ -0,0 +1,37 @@
+//! Compare the serial and parallel candidate scans with:
+//!
+//! ```sh
+//! cargo bench --bench part_2 -- --save-baseline serial
+//! cargo bench --bench part_2 --features parallel -- --baseline serial
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc07::part_2;
+
+/// Generates `n` crab positions in `[0, max)` using a fixed-seed LCG, so that
+/// every run benchmarks the same input.
+fn generate_input(n: usize, max: u32) -> Vec<u32> {
+    let mut state: u64 = 0x2021_0007;
+
+    let mut input = (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % max as u64) as u32
+        })
+        .collect::<Vec<_>>();
+
+    input.sort_unstable();
+    input
+}
+
+fn bench_part_2(c: &mut Criterion) {
+    let input = generate_input(1_000, 1_500);
+
+    c.bench_function("part_2 1k crabs", |b| b.iter(|| part_2(black_box(&input))));
+}
+
+criterion_group!(benches, bench_part_2);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+use aoc::arith::add;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub use aoc::arith::OverflowError;
+
+pub fn part_1(input: &[u32]) -> Result<u32, OverflowError> {
+    // The median value gives us the necessary position, because
+    // the property of the median is that it minimizes the distance
+    // between the values in the set.
+    // We assume that input is sorted.
+    let pos = input[input.len().div_ceil(2)];
+    cost_linear(input, pos)
+}
+
+pub fn part_2(input: &[u32]) -> Result<u32, OverflowError> {
+    // Totals are summed in a u64, as a single far-off crab can push the cost
+    // of the worst candidates past a u32 even when the lowest cost fits.
+    let total_cost = |pos| {
+        input
+            .iter()
+            .try_fold(0, |acc, &x| add(acc, cost_nonlinear(x, pos)))
+    };
+
+    // Assume that the ideal position lies in [0, max(input)]
+    let candidates = 0..=*input.iter().max().unwrap();
+
+    #[cfg(feature = "parallel")]
+    let lowest_cost = candidates
+        .into_par_iter()
+        .map(total_cost)
+        .try_reduce(|| u64::MAX, |a, b| Ok(u64::min(a, b)));
+
+    #[cfg(not(feature = "parallel"))]
+    let lowest_cost = candidates
+        .map(total_cost)
+        .try_fold(u64::MAX, |lowest, cost| Ok(u64::min(lowest, cost?)));
+
+    u32::try_from(lowest_cost?).map_err(|_| OverflowError)
+}
+
+fn cost_linear(input: &[u32], pos: u32) -> Result<u32, OverflowError> {
+    input.iter().try_fold(0, |acc, x| add(acc, x.abs_diff(pos)))
+}
+
+fn cost_nonlinear(cur: u32, desired: u32) -> u64 {
+    let diff = u64::from(cur.abs_diff(desired));
+    // sum of natural numbers [1, diff], which always fits in a u64 even when
+    // diff * (diff + 1) alone wouldn't fit in a u32
+    diff * (diff + 1) / 2
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(cost_linear(&[0, 1, 1, 2, 2, 2, 4, 7, 14, 16], 2), Ok(37));
+        assert_eq!(cost_linear(&[0, 1, 1, 2, 2, 2, 4, 7, 14, 16], 1), Ok(41));
+        assert_eq!(cost_linear(&[0, 1, 1, 2, 2, 2, 4, 7, 14, 16], 3), Ok(39));
+        assert_eq!(cost_linear(&[0, 1, 1, 2, 2, 2, 4, 7, 14, 16], 10), Ok(71));
+    }
+
+    #[test]
+    fn test_cost_nonlinear() {
+        assert_eq!(cost_nonlinear(3, 5), 3);
+        assert_eq!(cost_nonlinear(16, 5), 66);
+        assert_eq!(cost_nonlinear(5, 5), 0);
+        // diff * (diff + 1) overflows a u32 here, but the cost doesn't.
+        assert_eq!(cost_nonlinear(0, 70_000), 2_450_035_000);
+        assert_eq!(cost_nonlinear(92_682, 0), 4_295_022_903);
+        assert_eq!(cost_nonlinear(0, u32::MAX), 9_223_372_034_707_292_160);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(&[0, 1, 1, 2, 2, 2, 4, 7, 14, 16]), Ok(168));
+        // Far-off candidates cost more than a u32 holds, the best one doesn't.
+        assert_eq!(part_2(&[0, 0, 0, 60_000]), Ok(1_350_045_000));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(cost_linear(&[0, u32::MAX, u32::MAX], 1), Err(OverflowError));
+        assert_eq!(part_2(&[0, 200_000]), Err(OverflowError));
+    }
+}